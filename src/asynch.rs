@@ -6,22 +6,117 @@ where
 {
     /// Creates a new driver instance for the LDC3114.
     pub fn new(i2c: I2C) -> Self {
-        Self {
-            i2c,
-            sency0: 4,
-            sency1: 4,
-            sency2: 4,
-            sency3: 4,
-            lcdiv: 3,
+        Self::from_parts(i2c, NoReset)
+    }
+
+    /// Creates a new driver instance for an LDC3114 that is already running.
+    ///
+    /// Unlike [`Self::new`], the cached sensor configuration is read from the
+    /// device, so derived values such as the sensor frequency returned by
+    /// [`Self::read_sensor_frequency`] are correct from the start. If the
+    /// device was left in configuration mode, it is returned to normal mode
+    /// to match the returned driver.
+    pub async fn new_attach(i2c: I2C) -> Result<Self, Error<E>> {
+        let mut ldc = Self::new(i2c);
+        if ldc.read_register(Register::Reset).await? & CONFIG_MODE != 0 {
+            ldc.write_register(Register::Reset, 0).await?;
         }
+        ldc.refresh_cache().await?;
+        Ok(ldc)
+    }
+
+    /// Creates a new driver instance and verifies that the device on the bus
+    /// is an LDC3114, see [`Self::verify_device`].
+    ///
+    /// Use this instead of [`Self::new`] to catch wiring mistakes or a wrong
+    /// part at startup rather than through bogus data later on.
+    pub async fn new_checked(i2c: I2C) -> Result<Self, Error<E>> {
+        let mut ldc = Self::new(i2c);
+        ldc.verify_device().await?;
+        Ok(ldc)
+    }
+}
+
+impl<I2C, E, P> Ldc3114<I2C, Normal, P>
+where
+    I2C: embedded_hal_async::i2c::I2c + embedded_hal::i2c::ErrorType<Error = E>,
+    P: embedded_hal::digital::OutputPin,
+{
+    /// Creates a new driver instance for an LDC3114 whose supply is switched
+    /// by `reset`.
+    ///
+    /// The LDC3114 has no reset pin, so `reset` is expected to enable its VDD
+    /// supply, e.g. through a load switch or regulator enable, with the device
+    /// powered while the pin is high. The pin isn't driven until
+    /// [`Self::hardware_reset`] is called, so the device is assumed to be
+    /// fresh out of reset like with [`Ldc3114::new`].
+    pub fn new_with_reset(i2c: I2C, reset: P) -> Self {
+        Self::from_parts(i2c, reset)
     }
 
+    /// Power-cycles the device through its supply enable, then waits until
+    /// the chip is ready.
+    ///
+    /// The pin is driven low for `off_time_ms` and then high again, after
+    /// which the device is given `power_up_ms` before STATUS:CHIP_READY is
+    /// polled. Both timings depend on the board rather than on the LDC3114:
+    /// `off_time_ms` must let VDD discharge below the power-on reset
+    /// threshold, and `power_up_ms` should cover the ramp of the supply. A
+    /// missing acknowledge while polling is treated as not ready yet rather
+    /// than as a bus error, so a short `power_up_ms` only costs polls.
+    /// Returns [`Error::Pin`] if the pin can't be driven and
+    /// [`Error::Timeout`] if the chip doesn't become ready.
+    ///
+    /// All registers return to their default values, which the cached sensor
+    /// configuration is reset to as well.
+    pub async fn hardware_reset<D: embedded_hal_async::delay::DelayNs>(
+        &mut self,
+        delay: &mut D,
+        off_time_ms: u32,
+        power_up_ms: u32,
+    ) -> Result<(), Error<E>> {
+        self.reset.set_low().map_err(|_| Error::Pin)?;
+        delay.delay_ms(off_time_ms).await;
+        self.reset.set_high().map_err(|_| Error::Pin)?;
+        delay.delay_ms(power_up_ms).await;
+        self.reset_state();
+
+        for _ in 0..MAX_POLLS {
+            match self.read_register(Register::Status).await {
+                Ok(sr) if sr & CHIP_READY != 0 => return Ok(()),
+                Ok(_) => {}
+                // The device doesn't acknowledge until it has powered up.
+                Err(Error::I2c(e)) if is_no_acknowledge::<I2C>(&e) => {}
+                Err(e) => return Err(e),
+            }
+            delay.delay_us(POLL_INTERVAL_US).await;
+        }
+        Err(Error::Timeout)
+    }
+}
+
+impl<I2C, E, MODE: Mode, RST> Ldc3114<I2C, MODE, RST>
+where
+    I2C: embedded_hal_async::i2c::I2c + embedded_hal::i2c::ErrorType<Error = E>,
+{
     /// Reads the device ID.
-    pub async fn read_device_id(&mut self) -> Result<u8, Error<E>> {
-        self.read_register(Register::DeviceIdMsb).await
+    ///
+    /// Compare the result with [`DEVICE_ID`] to check for the right part.
+    pub async fn read_device_id(&mut self) -> Result<u16, Error<E>> {
+        let mut buffer = [0; 2];
+        self.i2c
+            .write_read(I2C_ADDR, &[Register::DeviceIdLsb.addr()], &mut buffer)
+            .await
+            .map_err(Error::I2c)?;
+
+        let data = u16::from_le_bytes(buffer);
+        Ok(data)
     }
 
     /// Reads the manufacturer ID.
+    ///
+    /// Compare the result with [`MANUFACTURER_ID`] to check for a Texas
+    /// Instruments device.
     pub async fn read_manufacturer_id(&mut self) -> Result<u16, Error<E>> {
         let mut buffer = [0; 2];
         self.i2c
@@ -33,20 +128,91 @@ where
         Ok(data)
     }
 
+    /// Reads the manufacturer and device IDs in a single transaction, packed
+    /// with the manufacturer ID in the high half.
+    ///
+    /// Compare the result with [`EXPECTED_FULL_ID`] for a one-shot identity
+    /// check.
+    pub async fn full_id(&mut self) -> Result<u32, Error<E>> {
+        let mut buffer = [0; 4];
+        self.i2c
+            .write_read(I2C_ADDR, &[Register::ManufacturerIdLsb.addr()], &mut buffer)
+            .await
+            .map_err(Error::I2c)?;
+
+        let manufacturer_id = u16::from_le_bytes([buffer[0], buffer[1]]);
+        let device_id = u16::from_le_bytes([buffer[2], buffer[3]]);
+        Ok((u32::from(manufacturer_id) << 16) | u32::from(device_id))
+    }
+
+    /// Verifies that the device at the LDC3114 address is an LDC3114.
+    ///
+    /// Returns [`Error::UnexpectedDevice`] with the IDs that were read if they
+    /// don't match [`MANUFACTURER_ID`] and [`DEVICE_ID`], which usually
+    /// indicates an address conflict with another device on the bus or a
+    /// different part fitted to the footprint.
+    pub async fn verify_device(&mut self) -> Result<(), Error<E>> {
+        let id = self.full_id().await?;
+        check_full_id(id)
+    }
+
+    /// Checks that an LDC3114 is present on the bus, like
+    /// [`Self::verify_device`].
+    ///
+    /// Returns [`Error::NotPresent`] if the address isn't acknowledged, so
+    /// that an absent device can be told apart from other bus errors, which
+    /// are returned as [`Error::I2c`].
+    pub async fn probe(&mut self) -> Result<(), Error<E>> {
+        match self.verify_device().await {
+            Err(Error::I2c(e)) if is_no_acknowledge::<I2C>(&e) => Err(Error::NotPresent),
+            result => result,
+        }
+    }
+
+    /// Like [`Self::verify_device`], but reads the manufacturer and device IDs
+    /// twice and requires both reads to agree.
+    ///
+    /// Returns [`Error::UnstableRead`] if they disagree, so that a flaky bus
+    /// isn't mistaken for an unexpected device.
+    pub async fn verify_device_double_read(&mut self) -> Result<(), Error<E>> {
+        let first = self.full_id().await?;
+        let second = self.full_id().await?;
+        if first != second {
+            return Err(Error::UnstableRead);
+        }
+
+        check_full_id(first)
+    }
+
     /// Reads the status register.
+    ///
+    /// Note that a single read consumes all clear-on-read flags at once, i.e.
+    /// OUT_STATUS, MAXOUT, FSM_WD, LC_WD, TIMEOUT and REGISTER_FLAG. They are
+    /// kept in the status accumulated by the driver, see
+    /// [`Self::accumulated_status`].
     pub async fn read_status(&mut self) -> Result<Status, Error<E>> {
         let sr = self.read_register(Register::Status).await?;
+        Ok(Status::from_bits(sr))
+    }
 
-        Ok(Status {
-            output_status: (sr & OUT_STATUS != 0),
-            chip_ready: (sr & CHIP_READY != 0),
-            ready_to_write: (sr & RDY_TO_WRITE != 0),
-            maximum_output_code: (sr & MAXOUT != 0),
-            fsm_watchdog_error: (sr & FSM_WD != 0),
-            lc_sensor_watchdog_error: (sr & LC_WD != 0),
-            button_timeout: (sr & TIMEOUT != 0),
-            register_integrity_bad: (sr & REGISTER_FLAG != 0),
-        })
+    /// Reads the status register, returning both its raw value and the
+    /// decoded flags.
+    pub async fn read_status_raw(&mut self) -> Result<(u8, Status), Error<E>> {
+        let sr = self.read_register(Register::Status).await?;
+        Ok((sr, Status::from_bits(sr)))
+    }
+
+    /// Clears STATUS:OUT_STATUS by reading the status register.
+    ///
+    /// As reading the status register also clears the error flags, they are
+    /// merged into `acc` so they aren't lost.
+    pub async fn clear_output_status(
+        &mut self,
+        acc: &mut StatusAccumulator,
+    ) -> Result<(), Error<E>> {
+        let sr = self.read_register(Register::Status).await?;
+        acc.accumulate(sr);
+        Ok(())
     }
 
     /// Checks if the registers are ready to be written.
@@ -63,266 +229,1045 @@ where
         Ok(is_ready)
     }
 
-    /// Resets the device and register configurations.
+    /// Waits until the registers are ready to be written.
     ///
-    /// All registers will be returned to default values.
-    /// Normal operation will not resume until STATUS:CHIP_READY=1.
-    pub async fn full_reset(&mut self) -> Result<(), Error<E>> {
-        self.write_register(Register::Reset, FULL_RESET).await
+    /// Returns [`Error::Timeout`] if STATUS:RDY_TO_WRITE is not set within
+    /// a bounded number of polls.
+    pub async fn wait_for_ready_to_write<D: embedded_hal_async::delay::DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        self.poll_register_bits(Register::Status, RDY_TO_WRITE, delay)
+            .await
     }
 
-    /// Enter configuration mode.
+    /// Waits until the chip is ready after internal reset.
     ///
-    /// Any device configuration changes should be made in this mode.
-    pub async fn config_mode(&mut self) -> Result<(), Error<E>> {
-        self.write_register(Register::Reset, CONFIG_MODE).await
+    /// Returns [`Error::Timeout`] if STATUS:CHIP_READY is not set within
+    /// a bounded number of polls.
+    pub async fn wait_for_chip_ready<D: embedded_hal_async::delay::DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        self.poll_register_bits(Register::Status, CHIP_READY, delay)
+            .await
+    }
+
+    /// Waits until new data is available, i.e. OUT:DATA_RDY is set, reading
+    /// the OUT register every `poll_interval_us` microseconds.
+    ///
+    /// This doesn't rely on the INTB pin. Returns [`Error::Timeout`] if no
+    /// new data is reported within two seconds, longer than the period of the
+    /// slowest scan rate, so a dead sensor doesn't block forever. Like
+    /// [`Self::is_data_ready`], this clears DATA_RDY.
+    pub async fn wait_for_data_ready<D: embedded_hal_async::delay::DelayNs>(
+        &mut self,
+        delay: &mut D,
+        poll_interval_us: u32,
+    ) -> Result<(), Error<E>> {
+        let max_polls = DATA_READY_TIMEOUT_US / poll_interval_us.max(1);
+        for _ in 0..=max_polls {
+            if self.is_data_ready().await? {
+                return Ok(());
+            }
+            delay.delay_us(poll_interval_us).await;
+        }
+        Err(Error::Timeout)
     }
 
-    /// Enter normal mode (exit configuration mode).
-    pub async fn normal_mode(&mut self) -> Result<(), Error<E>> {
+    /// Polls a register until any of the given bits is set.
+    async fn poll_register_bits<D: embedded_hal_async::delay::DelayNs>(
+        &mut self,
+        register: Register,
+        bits: u8,
+        delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        for _ in 0..MAX_POLLS {
+            let value = self.read_register(register).await?;
+            if value & bits != 0 {
+                return Ok(());
+            }
+            delay.delay_us(POLL_INTERVAL_US).await;
+        }
+        Err(Error::Timeout)
+    }
+
+    /// Reads the sensor configuration needed by derived calculations from
+    /// the device and updates the cached values.
+    pub async fn refresh_cache(&mut self) -> Result<(), Error<E>> {
         let lcdiv = self.read_register(Register::LcDivider).await?;
+        let np_scan_rate = self.read_register(Register::NpScanRate).await?;
         let scfg0 = self.read_register(Register::Sensor0Config).await?;
         let scfg1 = self.read_register(Register::Sensor1Config).await?;
         let scfg2 = self.read_register(Register::Sensor2Config).await?;
         let scfg3 = self.read_register(Register::Sensor3Config).await?;
 
-        self.lcdiv = lcdiv & 0x07;
-        self.sency0 = scfg0 & 0x1F;
-        self.sency1 = scfg1 & 0x1F;
-        self.sency2 = scfg2 & 0x1F;
-        self.sency3 = scfg3 & 0x1F;
+        self.state.lcdiv = LC_DIVIDER.get(lcdiv);
+        self.state.scan_rate = NP_SCAN_RATE.get(np_scan_rate);
+        self.state.sency0 = SENCY.get(scfg0);
+        self.state.sency1 = SENCY.get(scfg1);
+        self.state.sency2 = SENCY.get(scfg2);
+        self.state.sency3 = SENCY.get(scfg3);
+        self.state.cache_valid = true;
+        Ok(())
+    }
 
-        self.write_register(Register::Reset, 0).await
+    /// Checks if new data is available, i.e. OUT:DATA_RDY is set.
+    ///
+    /// Note that reading the OUT register clears DATA_RDY, so a second call
+    /// returns `false` until the next conversion completes.
+    pub async fn is_data_ready(&mut self) -> Result<bool, Error<E>> {
+        let out = self.read_register(Register::Out).await?;
+        Ok(out & DATA_RDY != 0)
+    }
+
+    /// Reads the OUT register and decodes it into the channel output logic
+    /// states.
+    ///
+    /// This is the same as [`Self::read_output_logic_states`]. Note that
+    /// reading the OUT register clears DATA_RDY.
+    pub async fn read_output_states(&mut self) -> Result<OutputLogicStates, Error<E>> {
+        self.read_output_logic_states().await
+    }
+
+    /// Checks if the button output of the given channel is asserted, as
+    /// reported by its OUT register bit.
+    ///
+    /// Note that reading the OUT register clears DATA_RDY.
+    pub async fn is_button_pressed<T: ChannelRegisters>(
+        &mut self,
+        _ch: T,
+    ) -> Result<bool, Error<E>> {
+        let out = self.read_register(Register::Out).await?;
+        Ok(OutputLogicStates::from_bits(out).outputs()[T::CH as usize])
+    }
+
+    /// Reads the button outputs of all channels at once, indexed by channel
+    /// number.
+    ///
+    /// A single read of the OUT register gives a consistent snapshot of
+    /// simultaneously asserted buttons. Note that it clears DATA_RDY.
+    pub async fn pressed_channels(&mut self) -> Result<[bool; 4], Error<E>> {
+        let out = self.read_register(Register::Out).await?;
+        Ok(OutputLogicStates::from_bits(out).outputs())
+    }
+
+    /// Reads the button outputs of all channels at once and debounces them
+    /// with `scanner`.
+    ///
+    /// Note that reading the OUT register clears DATA_RDY.
+    pub async fn scan_buttons(
+        &mut self,
+        scanner: &mut ButtonScanner,
+    ) -> Result<ButtonScan, Error<E>> {
+        let outputs = self.pressed_channels().await?;
+        Ok(scanner.update(outputs))
     }
 
     /// Reads the channel output logic states.
     pub async fn read_output_logic_states(&mut self) -> Result<OutputLogicStates, Error<E>> {
         let out = self.read_register(Register::Out).await?;
 
-        Ok(OutputLogicStates {
-            new_data_available: (out & DATA_RDY != 0),
-            out0: (out & OUT0 != 0),
-            out1: (out & OUT1 != 0),
-            out2: (out & OUT2 != 0),
-            out3: (out & OUT3 != 0),
-        })
+        Ok(OutputLogicStates::from_bits(out))
+    }
+
+    /// Reads the processed data of the given channel and classifies it as
+    /// pressed or not with a software threshold.
+    ///
+    /// This is meant for detecting presses in firmware instead of with the
+    /// button algorithm of the device. The channel becomes pressed once its
+    /// data reaches `threshold` and released once the data drops below
+    /// `threshold - hysteresis`. The state of each channel is kept by the
+    /// driver between calls.
+    pub async fn read_press_state<T: ChannelRegisters>(
+        &mut self,
+        ch: T,
+        threshold: i16,
+        hysteresis: i16,
+    ) -> Result<bool, Error<E>> {
+        let data = self.read_button_data(ch).await?;
+        let bit = 1 << T::CH;
+        let pressed = press_state(
+            self.state.press_states & bit != 0,
+            data,
+            threshold,
+            hysteresis,
+        );
+        if pressed {
+            self.state.press_states |= bit;
+        } else {
+            self.state.press_states &= !bit;
+        }
+        Ok(pressed)
+    }
+
+    /// Checks if the baseline tracking of the given channel is currently
+    /// frozen.
+    ///
+    /// The baseline is frozen while the channel's baseline tracking pause is
+    /// enabled and its output is asserted, which explains processed data that
+    /// doesn't return to zero.
+    pub async fn is_baseline_frozen<T: ChannelRegisters>(
+        &mut self,
+        _ch: T,
+    ) -> Result<bool, Error<E>> {
+        let btpause = self.read_register(Register::BtPauseMaxWin).await?;
+        let out = self.read_register(Register::Out).await?;
+        let asserted = OutputLogicStates::from_bits(out).outputs()[T::CH as usize];
+        Ok(btpause & T::BTPAUSE_BIT != 0 && asserted)
+    }
+
+    /// Reads the STATUS and OUT registers in a single transaction.
+    ///
+    /// The returned [`Event`] can be queued and decoded later.
+    pub async fn read_event(&mut self) -> Result<Event, Error<E>> {
+        let mut buffer = [0; 2];
+        self.i2c
+            .write_read(I2C_ADDR, &[Register::Status.addr()], &mut buffer)
+            .await
+            .map_err(Error::I2c)?;
+
+        self.latch_status(buffer[0]);
+        Ok(Event::from_bytes(buffer))
     }
 
     /// Reads the button data for the given channel.
+    ///
+    /// This is the same as [`Self::read_data`].
     pub async fn read_button_data(&mut self, ch: impl ChannelRegisters) -> Result<i16, Error<E>> {
+        self.read_data(ch).await
+    }
+
+    /// Reads the processed data of the given channel.
+    ///
+    /// The DATA registers hold a signed 12-bit value from -0x800 to +0x7FF,
+    /// which is sign-extended to an `i16`. Both registers are read in a
+    /// single transaction, LSB first, so the value can't be torn across two
+    /// conversions.
+    pub async fn read_data<T: ChannelRegisters>(&mut self, ch: T) -> Result<i16, Error<E>> {
         let mut buffer = [0; 2];
         self.i2c
             .write_read(I2C_ADDR, &[ch.data_lsb() as u8], &mut buffer)
             .await
             .map_err(Error::I2c)?;
 
-        let data = i16::from_le_bytes(buffer);
-        Ok(data)
+        Ok(button_data_from_bytes(buffer[0], buffer[1]))
+    }
+
+    /// Reads the processed data of the given channel scaled to
+    /// `-full_scale..=full_scale`.
+    ///
+    /// The code range is mapped linearly, with -0x800 mapped to `-full_scale`
+    /// and +0x7FF to `full_scale`, using integer arithmetic that can't
+    /// overflow. `full_scale` must be positive, otherwise
+    /// [`Error::InvalidParameter`] is returned without touching the bus.
+    pub async fn read_data_scaled<T: ChannelRegisters>(
+        &mut self,
+        ch: T,
+        full_scale: i32,
+    ) -> Result<i32, Error<E>> {
+        if full_scale <= 0 {
+            return Err(Error::InvalidParameter);
+        }
+        let data = i64::from(self.read_data(ch).await?);
+        let max_code = if data < 0 { 0x800 } else { 0x7FF };
+        Ok((data * i64::from(full_scale) / max_code) as i32)
+    }
+
+    /// Reads the processed data of all channels in a single transaction.
+    ///
+    /// The values are sign-extended like with [`Self::read_data`] and
+    /// indexed by channel number.
+    pub async fn read_all_data(&mut self) -> Result<[i16; 4], Error<E>> {
+        let mut buffer = [0; 8];
+        self.i2c
+            .write_read(I2C_ADDR, &[Register::Data0Lsb.addr()], &mut buffer)
+            .await
+            .map_err(Error::I2c)?;
+
+        Ok(core::array::from_fn(|ch| {
+            button_data_from_bytes(buffer[2 * ch], buffer[2 * ch + 1])
+        }))
+    }
+
+    /// Reads the button data for the given channel along with its age.
+    ///
+    /// OUT:DATA_RDY is read (and thereby cleared) first, and if it is set,
+    /// `now` is recorded in `tracker` as the time of the latest new data.
+    /// The returned age is the time elapsed since then, or `None` if no new
+    /// data has been reported yet. An ever-growing age indicates that the
+    /// device stopped converting.
+    pub async fn read_data_with_age<C, T, D>(
+        &mut self,
+        ch: C,
+        now: T,
+        tracker: &mut DataAgeTracker<T>,
+    ) -> Result<(i16, Option<D>), Error<E>>
+    where
+        C: ChannelRegisters,
+        T: Copy + core::ops::Sub<Output = D>,
+    {
+        let out = self.read_register(Register::Out).await?;
+        let data = self.read_button_data(ch).await?;
+        Ok((data, tracker.update(out & DATA_RDY != 0, now)))
     }
 
-    /// Reads the pre-processed raw sensor data for the given channel.
+    /// Reads the processed data and button state of all channels.
+    ///
+    /// The STATUS, OUT and DATA registers are read in a single transaction,
+    /// so the returned states are coherent. Note that this clears the
+    /// clear-on-read flags of the STATUS and OUT registers.
+    pub async fn measure_all(&mut self) -> Result<[ChannelState; 4], Error<E>> {
+        let mut buffer = [0; 10];
+        self.measure_all_with_buffer(&mut buffer).await
+    }
+
+    /// Same as [`Self::measure_all`], using the given scratch buffer.
+    ///
+    /// `buffer` must be at least 10 bytes long, otherwise
+    /// [`Error::InvalidParameter`] is returned.
+    pub async fn measure_all_with_buffer(
+        &mut self,
+        buffer: &mut [u8],
+    ) -> Result<[ChannelState; 4], Error<E>> {
+        let buffer = buffer.get_mut(..10).ok_or(Error::InvalidParameter)?;
+        self.i2c
+            .write_read(I2C_ADDR, &[Register::Status.addr()], buffer)
+            .await
+            .map_err(Error::I2c)?;
+
+        self.latch_status(buffer[0]);
+        let out = buffer[1];
+        let state = |lsb: usize, bit: u8| ChannelState {
+            data: button_data_from_bytes(buffer[lsb], buffer[lsb + 1]),
+            pressed: out & bit != 0,
+        };
+
+        Ok([
+            state(2, OUT0),
+            state(4, OUT1),
+            state(6, OUT2),
+            state(8, OUT3),
+        ])
+    }
+
+    /// Measures statistics of the processed data of the given channel.
+    ///
+    /// `samples` new data samples are collected, waiting for OUT:DATA_RDY
+    /// before each of them with [`Self::wait_for_data_ready`], so even the
+    /// slowest scan rates in normal and low power mode are covered.
+    ///
+    /// To estimate the signal-to-noise ratio of a channel, measure once
+    /// without touching the sensor and once while pressing it, then compare
+    /// both measurements with [`SignalStats::snr`].
+    pub async fn measure_signal_stats<T, D>(
+        &mut self,
+        ch: T,
+        samples: u16,
+        delay: &mut D,
+    ) -> Result<SignalStats, Error<E>>
+    where
+        T: ChannelRegisters,
+        D: embedded_hal_async::delay::DelayNs,
+    {
+        if samples == 0 {
+            return Err(Error::InvalidParameter);
+        }
+
+        let mut sum = 0i32;
+        let mut min = i16::MAX;
+        let mut max = i16::MIN;
+        for _ in 0..samples {
+            self.wait_for_data_ready(delay, POLL_INTERVAL_US).await?;
+            let data = self.read_button_data(ch).await?;
+            sum += data as i32;
+            min = min.min(data);
+            max = max.max(data);
+        }
+
+        Ok(SignalStats {
+            mean: (sum / samples as i32) as i16,
+            min,
+            max,
+        })
+    }
+
+    /// Reads the processed data of all channels once and adds it to
+    /// `measurement`.
+    ///
+    /// Call this whenever new data is available, e.g. after
+    /// [`Self::wait_for_data_ready`], until enough samples were collected.
+    pub async fn accumulate_noise_sample(
+        &mut self,
+        measurement: &mut NoiseMeasurement,
+    ) -> Result<(), Error<E>> {
+        let data = self.read_all_data().await?;
+        measurement.add_sample(data);
+        Ok(())
+    }
+
+    /// Reads the 24-bit pre-processed raw sensor data for the given channel.
+    ///
+    /// The three RAW_DATA registers of a channel are read in a single
+    /// transaction, so the value can't be torn across two conversions.
+    /// Despite its name, RAW_DATAn_3 sits at the lowest address and holds
+    /// the most significant byte, followed by RAW_DATAn_2 and RAW_DATAn_1
+    /// with the least significant byte.
+    ///
+    /// Unlike the processed data returned by [`Self::read_data`], the raw
+    /// data is an unsigned 24-bit magnitude and must not be sign-extended.
+    pub async fn read_raw_data<T: ChannelRegisters>(&mut self, ch: T) -> Result<u32, Error<E>> {
+        let mut buffer = [0; 3];
+        self.i2c
+            .write_read(I2C_ADDR, &[ch.raw_data_start() as u8], &mut buffer)
+            .await
+            .map_err(Error::I2c)?;
+        Ok(raw_data_from_bytes(buffer))
+    }
+
+    /// Checks if the device is actively scanning the given channel, i.e. its
+    /// raw data changes over time.
+    ///
+    /// The raw data is re-read at the polling interval until it differs from
+    /// the first read, for at most the polling budget. Note that a perfectly
+    /// static sensor could read identical values and be reported as not
+    /// scanning, although conversion noise makes this unlikely in practice.
+    pub async fn is_scanning<T, D>(&mut self, ch: T, delay: &mut D) -> Result<bool, Error<E>>
+    where
+        T: ChannelRegisters,
+        D: embedded_hal_async::delay::DelayNs,
+    {
+        let first = self.read_raw_data(ch).await?;
+        for _ in 0..MAX_POLLS {
+            delay.delay_us(POLL_INTERVAL_US).await;
+            if self.read_raw_data(ch).await? != first {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Reads the raw data of all channels in a single transaction.
+    ///
+    /// The values are assembled like with [`Self::read_raw_data`] and indexed
+    /// by channel number.
+    pub async fn read_all_raw_data(&mut self) -> Result<[u32; 4], Error<E>> {
+        let mut buffer = [0; 12];
+        self.i2c
+            .write_read(I2C_ADDR, &[Register::RawData0_3.addr()], &mut buffer)
+            .await
+            .map_err(Error::I2c)?;
+
+        Ok(core::array::from_fn(|ch| {
+            raw_data_from_bytes([buffer[3 * ch], buffer[3 * ch + 1], buffer[3 * ch + 2]])
+        }))
+    }
+
+    /// Reads the raw data of the given channel and converts it to the sensor
+    /// frequency in Hz.
     ///
     /// The value returned is given by the following formula:
-    /// ```
+    /// ```text
     /// f_sensor = 30 * W * 44_000_000 / raw_data
     /// ```
     /// where
-    /// ```
+    /// ```text
     /// W = 128 * (1 + SENCY_n) * (2^LCDIV)
     /// ```
-    pub async fn read_raw_data<T: ChannelRegisters>(&mut self, ch: T) -> Result<u32, Error<E>> {
-        let mut buffer = [0; 4];
-        let slice = &mut buffer[0..=2];
+    ///
+    /// Returns [`Error::CacheInvalidated`] if the cached sensor configuration
+    /// is unknown.
+    pub async fn read_sensor_frequency<T: ChannelRegisters>(
+        &mut self,
+        ch: T,
+    ) -> Result<u32, Error<E>> {
+        if !self.state.cache_valid {
+            return Err(Error::CacheInvalidated);
+        }
+
+        let data = self.read_raw_data(ch).await?;
+        self.sensor_frequency_hz(ch, data, REFERENCE_CLOCK_HZ)
+            .ok_or(Error::CacheInvalidated)
+    }
+
+    /// Reads the raw data of the given channel like [`Self::read_raw_data`],
+    /// retrying up to `retries` times while it is zero.
+    ///
+    /// A raw data of zero usually means that the read caught the device
+    /// mid-conversion or before the oscillator stabilized, so each retry is
+    /// made after a delay of one polling interval. Zero can also be
+    /// legitimate, e.g. for a channel whose oscillator doesn't start, in
+    /// which case the last zero is returned once the retries are exhausted.
+    pub async fn read_raw_data_settled<T, D>(
+        &mut self,
+        ch: T,
+        delay: &mut D,
+        retries: u8,
+    ) -> Result<u32, Error<E>>
+    where
+        T: ChannelRegisters,
+        D: embedded_hal_async::delay::DelayNs,
+    {
+        let mut data = self.read_raw_data(ch).await?;
+        for _ in 0..retries {
+            if data != 0 {
+                break;
+            }
+            delay.delay_us(POLL_INTERVAL_US).await;
+            data = self.read_raw_data(ch).await?;
+        }
+        Ok(data)
+    }
+
+    /// Checks the health of the given channel.
+    ///
+    /// The channel is reported as oscillating if its raw data is nonzero, and
+    /// as railed if its processed data is at either end of its range.
+    pub async fn channel_health<T: ChannelRegisters>(
+        &mut self,
+        ch: T,
+    ) -> Result<ChannelHealth, Error<E>> {
+        let en = self.read_register(Register::En).await?;
+        let raw = self.read_raw_data(ch).await?;
+        let data = self.read_button_data(ch).await?;
+
+        Ok(ChannelHealth {
+            enabled: en & T::EN_BIT != 0,
+            oscillating: raw != 0,
+            railed: matches!(data, 0x7FF | -0x800),
+        })
+    }
+
+    /// Reads the EN register, so that the enabled channels can be restored
+    /// with [`restore_enable_state`](Self::restore_enable_state) later.
+    pub async fn save_enable_state(&mut self) -> Result<u8, Error<E>> {
+        self.read_register(Register::En).await
+    }
+
+    /// Reads the gain of the given channel.
+    ///
+    /// Reserved upper bits are masked off, so the result is in `0..=0x3F`.
+    pub async fn get_gain<T: ChannelRegisters>(&mut self, ch: T) -> Result<u8, Error<E>> {
+        let gain = self.read_register(ch.gain()).await?;
+        Ok(GAIN.get(gain))
+    }
+
+    /// Reads the gain of all channels.
+    ///
+    /// Reserved upper bits are masked off like with [`Self::get_gain`].
+    pub async fn read_gains(&mut self) -> Result<Gains, Error<E>> {
+        let mut buffer = [0; 7];
+        self.read_gains_with_buffer(&mut buffer).await
+    }
+
+    /// Reads the gain of all channels using the given scratch buffer.
+    ///
+    /// Reserved upper bits are masked off like with [`Self::get_gain`].
+    /// `buffer` must be at least 7 bytes long, otherwise
+    /// [`Error::InvalidParameter`] is returned.
+    pub async fn read_gains_with_buffer(&mut self, buffer: &mut [u8]) -> Result<Gains, Error<E>> {
+        let buffer = buffer.get_mut(..7).ok_or(Error::InvalidParameter)?;
+        self.i2c
+            .write_read(I2C_ADDR, &[Register::Gain0.addr()], buffer)
+            .await
+            .map_err(Error::I2c)?;
+
+        Ok(Gains {
+            ch0: GAIN.get(buffer[0]),
+            ch1: GAIN.get(buffer[2]),
+            ch2: GAIN.get(buffer[4]),
+            ch3: GAIN.get(buffer[6]),
+        })
+    }
+
+    /// Reads the event that drives pin INTB.
+    pub async fn read_interrupt_source(&mut self) -> Result<InterruptSource, Error<E>> {
+        let intpol = self.read_register(Register::IntPol).await?;
+        if intpol & BTN_ALG_EN != 0 {
+            Ok(InterruptSource::ButtonChange)
+        } else {
+            Ok(InterruptSource::DataReady)
+        }
+    }
+
+    /// Reads the Fast Tracking Factor (FTF) of all channels.
+    pub async fn read_all_ftf(&mut self) -> Result<[FastTrackingFactor; 4], Error<E>> {
+        let ftf0 = self.read_register(Register::Ftf0).await?;
+        let ftf1_2 = self.read_register(Register::Ftf1_2).await?;
+        let ftf3 = self.read_register(Register::Ftf3).await?;
+
+        Ok([
+            FastTrackingFactor::from_register(ftf0, ftf_field::<Channel0>()),
+            FastTrackingFactor::from_register(ftf1_2, ftf_field::<Channel1>()),
+            FastTrackingFactor::from_register(ftf1_2, ftf_field::<Channel2>()),
+            FastTrackingFactor::from_register(ftf3, ftf_field::<Channel3>()),
+        ])
+    }
+
+    /// Reads the contiguous block of configuration registers from EN (0x0C)
+    /// to CNTSC (0x1E) into `out`, in ascending address order.
+    ///
+    /// This is meant to be attached to bug reports, and can be decoded
+    /// offline with [`config_block_value`].
+    pub async fn read_raw_config_block(
+        &mut self,
+        out: &mut [u8; CONFIG_BLOCK_LEN],
+    ) -> Result<(), Error<E>> {
         self.i2c
-            .write_read(I2C_ADDR, &[ch.raw_data_lsb() as u8], slice)
+            .write_read(I2C_ADDR, &[Register::En.addr()], out)
+            .await
+            .map_err(Error::I2c)
+    }
+
+    /// Reads all configuration registers into a snapshot.
+    ///
+    /// The snapshot can later be compared against the device with
+    /// [`Self::check_against_golden`] to detect silent register corruption.
+    pub async fn capture_golden(&mut self) -> Result<GoldenSnapshot, Error<E>> {
+        let mut snapshot = [0; CONFIG_REGISTERS.len()];
+        for (value, register) in snapshot.iter_mut().zip(CONFIG_REGISTERS) {
+            *value = self.read_register(register).await?;
+        }
+        Ok(GoldenSnapshot(snapshot))
+    }
+
+    /// Reads the configuration registers and reconstructs the device
+    /// configuration, e.g. to compare it with the intended one.
+    ///
+    /// This is the inverse of [`Self::set_device_configuration`]. Returns
+    /// [`Error::InvalidEncoding`] if a register holds a value that isn't
+    /// representable in a [`DeviceConfig`].
+    pub async fn read_config(&mut self) -> Result<DeviceConfig, Error<E>> {
+        let snapshot = self.capture_golden().await?;
+        DeviceConfig::decode(|register| match register.config_index() {
+            Some(index) => snapshot.as_bytes()[index],
+            None => unreachable!("only configuration registers are decoded"),
+        })
+        .map_err(Error::InvalidEncoding)
+    }
+
+    /// Checks if the configuration registers still match the given snapshot.
+    pub async fn check_against_golden(
+        &mut self,
+        golden: &GoldenSnapshot,
+    ) -> Result<bool, Error<E>> {
+        Ok(self.capture_golden().await? == *golden)
+    }
+
+    /// Writes a value to a given register.
+    ///
+    /// Writing to a register that requires configuration mode from a driver
+    /// in [`Normal`] mode returns [`Error::NotReadyToWrite`]. Writes to RESET
+    /// are always allowed, but they bypass the mode tracked by the type of the
+    /// driver, see [`Ldc3114::config_mode`] and [`Ldc3114::normal_mode`] to
+    /// switch modes.
+    pub async fn write_register(&mut self, register: Register, value: u8) -> Result<(), Error<E>> {
+        if register.is_read_only() {
+            return Err(Error::WriteToReadOnly);
+        }
+
+        if register.requires_config_mode() && !MODE::CONFIG {
+            return Err(Error::NotReadyToWrite);
+        }
+
+        self.i2c
+            .write(I2C_ADDR, &[register.addr(), value])
+            .await
+            .map_err(Error::I2c)
+    }
+
+    /// Reads a value from a given register.
+    pub async fn read_register(&mut self, register: Register) -> Result<u8, Error<E>> {
+        let mut buffer = [0u8; 1];
+        self.i2c
+            .write_read(I2C_ADDR, &[register.addr()], &mut buffer)
             .await
             .map_err(Error::I2c)?;
 
-        let data = u32::from_le_bytes(buffer);
-        if data == 0 {
-            return Ok(0);
-        }
+        if let Register::Status = register {
+            self.latch_status(buffer[0]);
+        }
+        Ok(buffer[0])
+    }
+
+    /// Reads a value from the given kind of register of the given channel.
+    pub async fn read_channel_register<T: ChannelRegisters>(
+        &mut self,
+        ch: T,
+        which: ChannelRegister,
+    ) -> Result<u8, Error<E>> {
+        self.read_register(ch.register(which)).await
+    }
+
+    /// Modifies the value of a given register.
+    pub async fn modify_register<F>(&mut self, register: Register, f: F) -> Result<(), Error<E>>
+    where
+        F: FnOnce(u8) -> u8,
+    {
+        let value = self.read_register(register).await?;
+        self.write_register(register, f(value)).await
+    }
+
+    /// Sets a field of a given register, leaving the other bits untouched.
+    ///
+    /// Returns [`Error::InvalidParameter`] without touching the bus if `value`
+    /// doesn't fit in the field.
+    async fn modify_field(
+        &mut self,
+        register: Register,
+        field: BitField,
+        value: u8,
+    ) -> Result<(), Error<E>> {
+        if !field.fits(value) {
+            return Err(Error::InvalidParameter);
+        }
+        let current = self.read_register(register).await?;
+        let new = field.set(current, value).ok_or(Error::InvalidParameter)?;
+        self.write_register(register, new).await
+    }
+
+    /// Sets some bits of a given register.
+    pub async fn set_register_bits(
+        &mut self,
+        register: Register,
+        bits: u8,
+    ) -> Result<(), Error<E>> {
+        self.modify_register(register, |v| v | bits).await
+    }
+
+    /// Clears some bits of a given register.
+    pub async fn clear_register_bits(
+        &mut self,
+        register: Register,
+        bits: u8,
+    ) -> Result<(), Error<E>> {
+        self.modify_register(register, |v| v & !bits).await
+    }
+}
+
+impl<I2C, E, RST> Ldc3114<I2C, Normal, RST>
+where
+    I2C: embedded_hal_async::i2c::I2c + embedded_hal::i2c::ErrorType<Error = E>,
+{
+    /// Resets the device and register configurations.
+    ///
+    /// All registers will be returned to default values, which the cached
+    /// sensor configuration is reset to as well.
+    /// Normal operation will not resume until STATUS:CHIP_READY=1, see
+    /// [`Self::reset`] to wait for it.
+    pub async fn full_reset(&mut self) -> Result<(), Error<E>> {
+        self.write_register(Register::Reset, FULL_RESET).await?;
+        self.reset_state();
+        Ok(())
+    }
+
+    /// Resets the device like [`Self::full_reset`] and waits until the chip
+    /// is ready.
+    ///
+    /// Returns [`Error::Timeout`] if STATUS:CHIP_READY doesn't assert within
+    /// the polling budget, which tells a stuck device apart from bus errors.
+    pub async fn reset<D: embedded_hal_async::delay::DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        self.full_reset().await?;
+        self.wait_for_chip_ready(delay).await
+    }
+
+    /// Enters configuration mode without waiting for the registers to become
+    /// ready to be written.
+    ///
+    /// Any device configuration changes should be made in this mode, and the
+    /// setters of configuration registers are only available on the returned
+    /// driver. See [`Self::enter_config_mode`] to also wait for
+    /// STATUS:RDY_TO_WRITE. On failure, the driver is handed back in normal
+    /// mode along with the error.
+    pub async fn config_mode(
+        mut self,
+    ) -> Result<Ldc3114<I2C, Config, RST>, TransitionError<Self, E>> {
+        match self.write_register(Register::Reset, CONFIG_MODE).await {
+            Ok(()) => Ok(self.into_mode()),
+            Err(error) => Err(TransitionError { ldc: self, error }),
+        }
+    }
+
+    /// Enters configuration mode and confirms that the registers became
+    /// ready to be written.
+    ///
+    /// Returns [`Error::Timeout`] if STATUS:RDY_TO_WRITE doesn't assert
+    /// within the polling budget, e.g. because the device was busy. The
+    /// device is then returned to normal mode on a best-effort basis and the
+    /// driver is handed back in normal mode along with the error.
+    pub async fn enter_config_mode<D: embedded_hal_async::delay::DelayNs>(
+        self,
+        delay: &mut D,
+    ) -> Result<Ldc3114<I2C, Config, RST>, TransitionError<Self, E>> {
+        let mut ldc = self.config_mode().await?;
+        match ldc.wait_for_ready_to_write(delay).await {
+            Ok(()) => Ok(ldc),
+            Err(error) => {
+                // The wait error is reported, even if leaving configuration
+                // mode fails as well.
+                let _ = ldc.leave_config_mode().await;
+                Err(TransitionError {
+                    ldc: ldc.into_mode(),
+                    error,
+                })
+            }
+        }
+    }
+
+    /// Runs `f` in configuration mode.
+    ///
+    /// The device enters configuration mode and `f` runs once registers are
+    /// ready to be written, with a driver in [`Config`] mode borrowing the bus.
+    /// Normal mode is restored afterwards, even if `f` or the wait returned an
+    /// error, in which case that error is returned. Changes to the cached
+    /// state made through the borrowed driver are kept.
+    pub async fn with_config_session<R, D: embedded_hal_async::delay::DelayNs>(
+        &mut self,
+        delay: &mut D,
+        f: impl AsyncFnOnce(&mut Ldc3114<&mut I2C, Config>) -> Result<R, Error<E>>,
+    ) -> Result<R, Error<E>> {
+        self.write_register(Register::Reset, CONFIG_MODE).await?;
+        let mut ldc = Ldc3114 {
+            i2c: &mut self.i2c,
+            reset: NoReset,
+            mode: PhantomData::<Config>,
+            state: self.state,
+        };
+        let result = match ldc.wait_for_ready_to_write(delay).await {
+            Ok(()) => f(&mut ldc).await,
+            Err(e) => Err(e),
+        };
+        let exit = ldc.leave_config_mode().await;
+        self.state = ldc.state;
+        let value = result?;
+        exit?;
+        Ok(value)
+    }
+
+    /// Writes the entire device configuration, entering configuration mode
+    /// for the duration of the writes.
+    ///
+    /// The configuration is validated before anything is written, see
+    /// [`DeviceConfig::validate`]. Shared registers are composed from the
+    /// settings of all channels. Once
+    /// back in normal mode, the cached sensor configuration is refreshed from
+    /// the device. Unlike [`Self::configure_and_verify`], the configuration
+    /// isn't read back.
+    pub async fn apply_config<D: embedded_hal_async::delay::DelayNs>(
+        &mut self,
+        config: &DeviceConfig,
+        delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        config.validate().map_err(Error::InvalidConfig)?;
+        self.with_config_session(delay, async |ldc: &mut Ldc3114<&mut I2C, Config>| {
+            ldc.set_device_configuration(config).await
+        })
+        .await
+    }
+
+    /// Sets up the entire device configuration and verifies it.
+    ///
+    /// The configuration is written with [`Self::apply_config`], so normal
+    /// mode is restored even if a write fails. Once the chip is ready, the
+    /// configuration is read back and [`Error::Verification`] is returned if
+    /// any field defined by `config` doesn't match. Reserved bits are ignored.
+    pub async fn configure_and_verify<D: embedded_hal_async::delay::DelayNs>(
+        &mut self,
+        config: &DeviceConfig,
+        delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        self.apply_config(config, delay).await?;
+        self.wait_for_chip_ready(delay).await?;
+
+        for (register, value, mask) in config.encode() {
+            let actual = self.read_register(register).await?;
+            if actual & mask != value {
+                return Err(Error::Verification);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Brings up a unit on a production test fixture.
+    ///
+    /// The device is verified with [`Self::verify_device`], configured and
+    /// verified with [`Self::configure_and_verify`], and a snapshot of the
+    /// status and all channels is captured in a single transaction. The
+    /// reported status accumulates every status read made along the way,
+    /// including the readiness polls, so a fault flag cleared by an earlier
+    /// read isn't missed. Flags accumulated before the call are discarded. A
+    /// configuration mismatch or an error flag is reported as a failing
+    /// [`BringupReport`], while other errors are returned as is.
+    pub async fn production_bringup<D: embedded_hal_async::delay::DelayNs>(
+        &mut self,
+        config: &DeviceConfig,
+        delay: &mut D,
+    ) -> Result<BringupReport, Error<E>> {
+        self.take_status();
+        self.verify_device().await?;
+        let config_verified = match self.configure_and_verify(config, delay).await {
+            Ok(()) => true,
+            Err(Error::Verification) => false,
+            Err(e) => return Err(e),
+        };
+
+        let mut buffer = [0; 10];
+        let channels = self.measure_all_with_buffer(&mut buffer).await?;
+        Ok(BringupReport {
+            config_verified,
+            status: self.take_status(),
+            channels,
+        })
+    }
+
+    /// Temporarily sets the scan rate in normal power mode while running `f`.
+    ///
+    /// The cached scan rate returned by [`Self::scan_rate`] is saved and
+    /// restored afterwards with [`Self::set_scan_rate`], so only the scan rate
+    /// field of NP_SCAN_RATE is written. The scan rate can only be written in
+    /// configuration mode, so the device briefly enters it around each change,
+    /// which is why this is only available in normal mode. `delay` is used to
+    /// poll STATUS:RDY_TO_WRITE after each entry, as the device may not accept
+    /// writes right away.
+    ///
+    /// Returns [`Error::CacheInvalidated`] without touching the device if the
+    /// cached scan rate is unknown.
+    pub async fn with_scan_rate<R, D: embedded_hal_async::delay::DelayNs>(
+        &mut self,
+        sr: ScanRate,
+        delay: &mut D,
+        f: impl AsyncFnOnce(&mut Self) -> R,
+    ) -> Result<R, Error<E>> {
+        let previous = self.scan_rate().ok_or(Error::CacheInvalidated)?;
+        self.with_config_session(delay, async |ldc: &mut Ldc3114<&mut I2C, Config>| {
+            ldc.set_scan_rate(sr).await
+        })
+        .await?;
+        let result = f(self).await;
+        self.with_config_session(delay, async |ldc: &mut Ldc3114<&mut I2C, Config>| {
+            ldc.set_scan_rate(previous).await
+        })
+        .await?;
+        Ok(result)
+    }
+
+    /// Advances `scanner` by one tick and enables only the channels that are
+    /// due in normal mode.
+    ///
+    /// Channels that aren't due are disabled in both normal and low power
+    /// mode, so their LPEN bits are cleared, while due channels keep theirs.
+    /// Save the enable state with [`Self::save_enable_state`] before
+    /// scheduling to restore it afterwards. Returns the enabled channels, whose
+    /// data can be read once new data is available. Like
+    /// [`Self::with_scan_rate`], this briefly enters configuration mode and
+    /// is only available in normal mode. See [`ScheduledScanner`] for the
+    /// limitations of this approach.
+    pub async fn scan_scheduled<D: embedded_hal_async::delay::DelayNs>(
+        &mut self,
+        scanner: &mut ScheduledScanner,
+        delay: &mut D,
+    ) -> Result<ChannelSet, Error<E>> {
+        let due = scanner.tick();
+        let en = self.read_register(Register::En).await?;
+        self.write_in_config_mode(Register::En, (en & (due.bits() << 4)) | due.bits(), delay)
+            .await?;
+        Ok(due)
+    }
+
+    /// Temporarily disables the button algorithm while running `f`, e.g. to
+    /// capture raw measurements.
+    ///
+    /// The previous state of INTPOL:BTN_ALG_EN is restored afterwards. Like
+    /// [`Self::with_scan_rate`], this briefly enters configuration mode around
+    /// each change and is only available in normal mode.
+    pub async fn with_button_algorithm_disabled<R, D: embedded_hal_async::delay::DelayNs>(
+        &mut self,
+        delay: &mut D,
+        f: impl AsyncFnOnce(&mut Self) -> R,
+    ) -> Result<R, Error<E>> {
+        let intpol = self.read_register(Register::IntPol).await?;
+        self.write_in_config_mode(Register::IntPol, intpol & !BTN_ALG_EN, delay)
+            .await?;
+        let result = f(self).await;
+        let current = self.read_register(Register::IntPol).await?;
+        let restored = (current & !BTN_ALG_EN) | (intpol & BTN_ALG_EN);
+        self.write_in_config_mode(Register::IntPol, restored, delay)
+            .await?;
+        Ok(result)
+    }
+
+    /// Writes a register from normal mode by briefly entering configuration mode.
+    async fn write_in_config_mode<D: embedded_hal_async::delay::DelayNs>(
+        &mut self,
+        register: Register,
+        value: u8,
+        delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        self.with_config_session(delay, async |ldc: &mut Ldc3114<&mut I2C, Config>| {
+            ldc.write_register(register, value).await
+        })
+        .await
+    }
+}
+
+impl<I2C, E, RST> Ldc3114<I2C, Config, RST>
+where
+    I2C: embedded_hal_async::i2c::I2c + embedded_hal::i2c::ErrorType<Error = E>,
+{
+    /// Exits configuration mode entered with [`Ldc3114::enter_config_mode`].
+    ///
+    /// This is the same as [`Self::normal_mode`], so several setters can be
+    /// batched between a single enter/exit pair.
+    pub async fn exit_config_mode(
+        self,
+    ) -> Result<Ldc3114<I2C, Normal, RST>, TransitionError<Self, E>> {
+        self.normal_mode().await
+    }
 
-        let sency = match T::CH {
-            0 => self.sency0,
-            1 => self.sency1,
-            2 => self.sency2,
-            3 => self.sency3,
-            _ => unreachable!(),
-        };
+    /// Enters normal mode (exits configuration mode).
+    ///
+    /// The cached sensor configuration is refreshed from the device first, as
+    /// it may have been changed. On failure, the driver is handed back in
+    /// configuration mode along with the error.
+    pub async fn normal_mode(
+        mut self,
+    ) -> Result<Ldc3114<I2C, Normal, RST>, TransitionError<Self, E>> {
+        match self.leave_config_mode().await {
+            Ok(()) => Ok(self.into_mode()),
+            Err(error) => Err(TransitionError { ldc: self, error }),
+        }
+    }
 
-        let w = 128 * (1 + sency as u32) * (2 << self.lcdiv as u32);
-        let fsensor = 30 * w as u64 * 44_000_000 / data as u64;
-        Ok(fsensor as u32)
+    /// Refreshes the cache and returns the device to normal mode, leaving
+    /// the type of the driver to the caller.
+    async fn leave_config_mode(&mut self) -> Result<(), Error<E>> {
+        self.refresh_cache().await?;
+        self.write_register(Register::Reset, 0).await
     }
 
     /// Sets up the entire device configuration.
+    ///
+    /// Returns [`Error::InvalidConfig`] without writing anything if the
+    /// configuration doesn't pass [`DeviceConfig::validate`].
     pub async fn set_device_configuration(
         &mut self,
         config: &DeviceConfig,
     ) -> Result<(), Error<E>> {
-        fn en_bits<T: ChannelRegisters>(_ch: T, mode: ChannelMode) -> u8 {
-            match mode {
-                ChannelMode::Disabled => 0x00,
-                ChannelMode::NormalMode => T::EN_BIT,
-                ChannelMode::NormalAndLowPowerMode => T::EN_BIT | T::LPEN_BIT,
-            }
-        }
-
-        fn btpause_maxwin_bits<T: ChannelRegisters>(_ch: T, btpause: bool, maxwin: bool) -> u8 {
-            match (btpause, maxwin) {
-                (false, false) => 0x00,
-                (true, false) => T::BTPAUSE_BIT,
-                (false, true) => T::MAXWIN_BIT,
-                (true, true) => T::BTPAUSE_BIT | T::MAXWIN_BIT,
-            }
-        }
-
-        fn common_deform_bits<T: ChannelRegisters>(_ch: T, common: bool, deform: bool) -> u8 {
-            match (common, deform) {
-                (false, false) => 0x00,
-                (true, false) => T::ANTICOM_BIT,
-                (false, true) => T::ANTIDFORM_BIT,
-                (true, true) => T::ANTICOM_BIT | T::ANTIDFORM_BIT,
-            }
-        }
-
-        fn opol_dpol_bits<T: ChannelRegisters>(
-            _ch: T,
-            opol: OutputPolarity,
-            dpol: DataPolarity,
-        ) -> u8 {
-            match (opol, dpol) {
-                (OutputPolarity::ActiveLow, DataPolarity::Inverted) => 0x00,
-                (OutputPolarity::ActiveHigh, DataPolarity::Inverted) => T::OPOL_BIT,
-                (OutputPolarity::ActiveLow, DataPolarity::Normal) => T::DPOL_BIT,
-                (OutputPolarity::ActiveHigh, DataPolarity::Normal) => T::OPOL_BIT | T::DPOL_BIT,
+        config.validate().map_err(Error::InvalidConfig)?;
+
+        for (register, value, mask) in config.encode() {
+            if mask == 0xFF {
+                self.write_register(register, value).await?;
+            } else {
+                self.modify_register(register, |v| (v & !mask) | value)
+                    .await?;
             }
         }
 
-        let mut en = en_bits(Channel0, config.ch0.mode);
-        en |= en_bits(Channel1, config.ch1.mode);
-        en |= en_bits(Channel2, config.ch2.mode);
-        en |= en_bits(Channel3, config.ch3.mode);
-        self.write_register(Register::En, en).await?;
-
-        self.set_channel_gain(Channel0, config.ch0.gain).await?;
-        self.set_channel_gain(Channel1, config.ch1.gain).await?;
-        self.set_channel_gain(Channel2, config.ch2.gain).await?;
-        self.set_channel_gain(Channel3, config.ch3.gain).await?;
-
-        self.set_normal_scan_rate(config.scan_rate).await?;
-        self.set_low_power_scan_rate(config.low_power_scan_rate)
-            .await?;
-
-        let mut intpol = (config.enable_reset_of_button_baseline_tracking as u8) << 4;
-        intpol |= (config.enable_button_press_detection_algorithm as u8) << 3;
-        intpol |= (config.interrupt_polarity as u8) << 2;
-        intpol |= ((!config.enable_button_timeout) as u8) << 1;
-        intpol |= (!config.enable_max_out_check) as u8;
-        self.write_register(Register::IntPol, intpol).await?;
-
-        self.set_baseline_tracking_increment_np(config.baseline_tracking_increment_np)
-            .await?;
-        self.set_baseline_tracking_increment_lp(config.baseline_tracking_increment_lp)
-            .await?;
-
-        let mut btpause_maxwin = btpause_maxwin_bits(
-            Channel0,
-            config.ch0.baseline_tracking_pause,
-            config.ch0.enable_max_win_button_algorithm,
-        );
-        btpause_maxwin |= btpause_maxwin_bits(
-            Channel1,
-            config.ch1.baseline_tracking_pause,
-            config.ch1.enable_max_win_button_algorithm,
-        );
-        btpause_maxwin |= btpause_maxwin_bits(
-            Channel2,
-            config.ch2.baseline_tracking_pause,
-            config.ch2.enable_max_win_button_algorithm,
-        );
-        btpause_maxwin |= btpause_maxwin_bits(
-            Channel3,
-            config.ch3.baseline_tracking_pause,
-            config.ch3.enable_max_win_button_algorithm,
-        );
-        self.write_register(Register::BtPauseMaxWin, btpause_maxwin)
-            .await?;
-
-        self.set_lc_divider(config.lc_divider).await?;
-        self.set_hysteresis(config.hysteresis).await?;
-        self.set_antitwist(config.antitwist).await?;
-
-        let mut common_deform = common_deform_bits(
-            Channel0,
-            config.ch0.enable_anticommon_algorithm,
-            config.ch0.enable_antideform_algorithm,
-        );
-        common_deform |= common_deform_bits(
-            Channel1,
-            config.ch1.enable_anticommon_algorithm,
-            config.ch1.enable_antideform_algorithm,
-        );
-        common_deform |= common_deform_bits(
-            Channel2,
-            config.ch2.enable_anticommon_algorithm,
-            config.ch2.enable_antideform_algorithm,
-        );
-        common_deform |= common_deform_bits(
-            Channel3,
-            config.ch3.enable_anticommon_algorithm,
-            config.ch3.enable_antideform_algorithm,
-        );
-        self.write_register(Register::CommonDeform, common_deform)
-            .await?;
-
-        let mut opol_dpol = opol_dpol_bits(
-            Channel0,
-            config.ch0.output_polarity,
-            config.ch0.data_polarity,
-        );
-        opol_dpol |= opol_dpol_bits(
-            Channel1,
-            config.ch1.output_polarity,
-            config.ch1.data_polarity,
-        );
-        opol_dpol |= opol_dpol_bits(
-            Channel2,
-            config.ch2.output_polarity,
-            config.ch2.data_polarity,
-        );
-        opol_dpol |= opol_dpol_bits(
-            Channel3,
-            config.ch3.output_polarity,
-            config.ch3.data_polarity,
-        );
-        self.write_register(Register::OpolDpol, opol_dpol).await?;
-
-        let mut cntsc = (config.ch3.counter_scale as u8) << 6;
-        cntsc |= (config.ch2.counter_scale as u8) << 4;
-        cntsc |= (config.ch1.counter_scale as u8) << 2;
-        cntsc |= config.ch1.counter_scale as u8;
-        self.write_register(Register::Cntsc, cntsc).await?;
-
-        self.set_sensor_config(Channel0, &config.ch0.sensor_config)
-            .await?;
-        self.set_sensor_config(Channel1, &config.ch1.sensor_config)
-            .await?;
-        self.set_sensor_config(Channel2, &config.ch2.sensor_config)
-            .await?;
-        self.set_sensor_config(Channel3, &config.ch3.sensor_config)
-            .await?;
-
-        self.set_fast_tracking_factor(Channel0, config.ch0.fast_tracking_factor)
-            .await?;
-        self.set_fast_tracking_factor(Channel3, config.ch3.fast_tracking_factor)
-            .await?;
-
-        let mut ftf1_2 = (config.ch2.fast_tracking_factor as u8) << 6;
-        ftf1_2 |= (config.ch1.fast_tracking_factor as u8) << 4;
-        self.write_register(Register::Ftf1_2, ftf1_2).await?;
-
         Ok(())
     }
 
@@ -377,24 +1322,125 @@ where
         }
     }
 
+    /// Restores the EN register value returned by
+    /// [`save_enable_state`](Self::save_enable_state).
+    ///
+    /// Like any write to EN, this requires configuration mode.
+    pub async fn restore_enable_state(&mut self, saved: u8) -> Result<(), Error<E>> {
+        self.write_register(Register::En, saved).await
+    }
+
+    /// Disables all channels in both normal and low power mode.
+    ///
+    /// This is useful to pause sensing while an adjacent subsystem injects
+    /// interference. Save the enable state first to restore it afterwards.
+    /// Like any write to EN, this requires configuration mode.
+    pub async fn disable_all_channels(&mut self) -> Result<(), Error<E>> {
+        self.write_register(Register::En, 0x00).await
+    }
+
+    /// Sets all channels to the given operating mode.
+    ///
+    /// Like any write to EN, this requires configuration mode.
+    pub async fn enable_all_channels(&mut self, mode: ChannelMode) -> Result<(), Error<E>> {
+        let en = Channel0::EN_BIT | Channel1::EN_BIT | Channel2::EN_BIT | Channel3::EN_BIT;
+        let lpen =
+            Channel0::LPEN_BIT | Channel1::LPEN_BIT | Channel2::LPEN_BIT | Channel3::LPEN_BIT;
+        let value = match mode {
+            ChannelMode::Disabled => 0x00,
+            ChannelMode::NormalMode => en,
+            ChannelMode::NormalAndLowPowerMode => en | lpen,
+        };
+        self.write_register(Register::En, value).await
+    }
+
+    /// Disables the given channel and puts its sensor configuration in a
+    /// benign state.
+    ///
+    /// Besides clearing the channel's EN and LPEN bits, the gain is set to 0
+    /// and the sensor is configured with a cycle count of 0 and the lowest
+    /// Rp and frequency ranges.
+    pub async fn disable_channel_fully<T: ChannelRegisters>(
+        &mut self,
+        ch: T,
+    ) -> Result<(), Error<E>> {
+        const DISABLED_SENSOR_CONFIG: SensorConfig = SensorConfig {
+            rp_range: RpRange::Rp50OhmTo4kOhm,
+            frequency_range: FrequencyRange::Freq1MHzTo3_3MHz,
+            cycle_count: 0,
+        };
+
+        self.set_channel_mode(ch, ChannelMode::Disabled).await?;
+        self.set_channel_gain(ch, 0).await?;
+        self.set_sensor_config(ch, &DISABLED_SENSOR_CONFIG).await
+    }
+
     /// Sets the gain for the given channel.
+    ///
+    /// This is the same as [`Self::set_gain`].
     pub async fn set_channel_gain<T: ChannelRegisters>(
         &mut self,
         ch: T,
         gain: u8,
     ) -> Result<(), Error<E>> {
-        if gain >= 0x40 {
-            return Err(Error::InvalidParameter);
-        }
-        self.write_register(ch.gain(), gain).await
+        self.set_gain(ch, gain).await
+    }
+
+    /// Sets the gain for the given channel.
+    ///
+    /// The gain is 6 bits wide, so values above 0x3F are rejected with
+    /// [`Error::InvalidParameter`] instead of being truncated. The reserved
+    /// upper bits of the register are preserved.
+    pub async fn set_gain<T: ChannelRegisters>(&mut self, ch: T, gain: u8) -> Result<(), Error<E>> {
+        self.modify_field(ch.gain(), GAIN, gain).await
     }
 
     /// Sets the scan rate in normal power mode.
+    ///
+    /// This is the same as [`Self::set_scan_rate`].
     pub async fn set_normal_scan_rate(&mut self, sr: ScanRate) -> Result<(), Error<E>> {
-        self.write_register(Register::NpScanRate, sr as u8).await
+        self.set_scan_rate(sr).await
+    }
+
+    /// Sets the scan rate in normal power mode.
+    ///
+    /// Only the scan rate field in the lower bits of NP_SCAN_RATE is
+    /// modified, the reserved upper bits are preserved.
+    ///
+    /// The cached scan rate returned by [`Self::scan_rate`] is updated along
+    /// with the register.
+    pub async fn set_scan_rate(&mut self, sr: ScanRate) -> Result<(), Error<E>> {
+        self.modify_field(Register::NpScanRate, NP_SCAN_RATE, sr as u8)
+            .await?;
+        self.state.scan_rate = sr as u8;
+        Ok(())
+    }
+
+    /// Sets the scan rate in normal power mode and returns the resulting
+    /// samples per second of each enabled channel.
+    ///
+    /// The cached scan rate is updated like with [`Self::set_scan_rate`].
+    /// The scan rate sets the interval between scans, not between
+    /// conversions, and every scan converts all enabled channels in
+    /// sequence. The enabled-channel count therefore doesn't divide the
+    /// rate, and each enabled channel is sampled at the nominal scan rate.
+    /// Returns 0 if no channel is enabled and
+    /// `u16::MAX` for [`ScanRate::Continuous`], whose rate depends on the
+    /// conversion time of the enabled channels.
+    pub async fn set_scan_rate_reporting(&mut self, sr: ScanRate) -> Result<u16, Error<E>> {
+        self.set_normal_scan_rate(sr).await?;
+        let en = self.read_register(Register::En).await?;
+        if en & (EN0 | EN1 | EN2 | EN3) == 0 {
+            return Ok(0);
+        }
+        Ok(sr.sps())
     }
 
     /// Sets the scan rate in low power mode.
+    ///
+    /// Like all configuration registers, LP_SCAN_RATE can only be written in
+    /// configuration mode once STATUS:RDY_TO_WRITE is set, see
+    /// [`Ldc3114::enter_config_mode`].
     pub async fn set_low_power_scan_rate(&mut self, sr: LowPowerScanRate) -> Result<(), Error<E>> {
         self.write_register(Register::LpScanRate, sr as u8).await
     }
@@ -431,6 +1477,20 @@ where
         }
     }
 
+    /// Selects the event that drives pin INTB.
+    ///
+    /// See [`InterruptSource`] for the effect on the button algorithm.
+    /// [`InterruptSource::Both`] isn't supported by the device and returns
+    /// [`Error::InvalidParameter`] without touching the bus.
+    pub async fn set_interrupt_source(&mut self, source: InterruptSource) -> Result<(), Error<E>> {
+        let enable = match source {
+            InterruptSource::DataReady => false,
+            InterruptSource::ButtonChange => true,
+            InterruptSource::Both => return Err(Error::InvalidParameter),
+        };
+        self.enable_button_press_detection_algorithm(enable).await
+    }
+
     /// Enables/disables the button press detection algorithm to assert events on OUT_X pins.
     pub async fn enable_button_press_detection_algorithm(
         &mut self,
@@ -456,19 +1516,39 @@ where
     }
 
     /// Sets the baseline tracking increment in normal power mode.
+    ///
+    /// This is the same as [`Self::set_baseline_increment_np`].
     pub async fn set_baseline_tracking_increment_np(&mut self, value: u8) -> Result<(), Error<E>> {
-        if value >= 0x08 {
-            return Err(Error::InvalidParameter);
-        }
-        self.write_register(Register::NpBaseInc, value).await
+        self.set_baseline_increment_np(value).await
+    }
+
+    /// Sets the baseline tracking increment in normal power mode.
+    ///
+    /// The increment is held in bits 2:0 of NP_BASE_INC, so the valid range
+    /// is `0..=0x07`. Other values are rejected with
+    /// [`Error::InvalidParameter`], and the other bits of the register are
+    /// preserved.
+    pub async fn set_baseline_increment_np(&mut self, value: u8) -> Result<(), Error<E>> {
+        self.modify_field(Register::NpBaseInc, BASE_INC, value)
+            .await
     }
 
     /// Sets the baseline tracking increment in low power mode.
+    ///
+    /// This is the same as [`Self::set_baseline_increment_lp`].
     pub async fn set_baseline_tracking_increment_lp(&mut self, value: u8) -> Result<(), Error<E>> {
-        if value >= 0x08 {
-            return Err(Error::InvalidParameter);
-        }
-        self.write_register(Register::LpBaseInc, value).await
+        self.set_baseline_increment_lp(value).await
+    }
+
+    /// Sets the baseline tracking increment in low power mode.
+    ///
+    /// The increment is held in bits 2:0 of LP_BASE_INC, so the valid range
+    /// is `0..=0x07`. Other values are rejected with
+    /// [`Error::InvalidParameter`], and the other bits of the register are
+    /// preserved.
+    pub async fn set_baseline_increment_lp(&mut self, value: u8) -> Result<(), Error<E>> {
+        self.modify_field(Register::LpBaseInc, BASE_INC, value)
+            .await
     }
 
     /// Configures baseline tracking to pause or not for the given channel
@@ -489,12 +1569,27 @@ where
 
     /// Configures whether to include or exclude the given channel
     /// from the Max-Win Button algorithm.
+    ///
+    /// Equivalent to [`Self::set_max_win`].
     pub async fn include_channel_in_max_win_algorithm<T: ChannelRegisters>(
         &mut self,
-        _ch: T,
+        ch: T,
         include: bool,
     ) -> Result<(), Error<E>> {
-        if include {
+        self.set_max_win(ch, include).await
+    }
+
+    /// Includes or excludes the given channel from the Max-Win Button
+    /// algorithm.
+    ///
+    /// Only the MAXWIN bit of the given channel is changed, the BTPAUSE bits
+    /// and the bits of the other channels are preserved.
+    pub async fn set_max_win<T: ChannelRegisters>(
+        &mut self,
+        _ch: T,
+        enable: bool,
+    ) -> Result<(), Error<E>> {
+        if enable {
             self.set_register_bits(Register::BtPauseMaxWin, T::MAXWIN_BIT)
                 .await
         } else {
@@ -503,38 +1598,74 @@ where
         }
     }
 
+    /// Sets which channels are included in the Max-Win Button algorithm.
+    ///
+    /// Channels not in `channels` are excluded from the algorithm.
+    pub async fn set_max_win_group(
+        &mut self,
+        channels: impl Into<ChannelSet>,
+    ) -> Result<(), Error<E>> {
+        let bits = channels.into().bits();
+        self.modify_field(Register::BtPauseMaxWin, MAXWIN_GROUP, bits)
+            .await
+    }
+
     /// Sets the LC oscillation frequency divider.
+    ///
+    /// The divider field is 3 bits wide, so the valid range is `0..=0x07`.
+    /// Other values are rejected with [`Error::InvalidParameter`], and the
+    /// reserved upper bits of the register are preserved. The cached divider
+    /// used by [`Self::read_sensor_frequency`] is updated along with the
+    /// register.
     pub async fn set_lc_divider(&mut self, value: u8) -> Result<(), Error<E>> {
-        if value >= 0x08 {
-            return Err(Error::InvalidParameter);
-        }
-        self.write_register(Register::LcDivider, value).await
+        self.modify_field(Register::LcDivider, LC_DIVIDER, value)
+            .await?;
+        self.state.lcdiv = value;
+        Ok(())
     }
 
-    /// Hysteresis for threshold for button algorithm.
+    /// Sets the hysteresis for the threshold of the button algorithm.
+    ///
+    /// The hysteresis field is 4 bits wide, so the valid range is
+    /// `0..=0x0F`. Other values are rejected with
+    /// [`Error::InvalidParameter`]. The reserved upper bits of the register
+    /// are preserved.
     pub async fn set_hysteresis(&mut self, value: u8) -> Result<(), Error<E>> {
-        if value >= 0x10 {
-            return Err(Error::InvalidParameter);
-        }
-        self.write_register(Register::Hyst, value).await
+        self.modify_field(Register::Hyst, HYST, value).await
     }
 
     /// Sets the anti-twist threshold value for the anti-twist button algorithm.
+    ///
+    /// The anti-twist field is 3 bits wide, so the valid range is `0..=0x07`.
+    /// Other values are rejected with [`Error::InvalidParameter`], and the
+    /// reserved upper bits of the register are preserved.
     pub async fn set_antitwist(&mut self, value: u8) -> Result<(), Error<E>> {
-        if value >= 0x08 {
-            return Err(Error::InvalidParameter);
-        }
-        self.write_register(Register::Twist, value).await
+        self.modify_field(Register::Twist, TWIST, value).await
     }
 
     /// Configures whether to include or exclude the given channel
     /// from the Anti-Common Button algorithm.
+    ///
+    /// Equivalent to [`Self::set_anticommon`].
     pub async fn include_channel_in_anticommon_algorithm<T: ChannelRegisters>(
         &mut self,
-        _ch: T,
+        ch: T,
         include: bool,
     ) -> Result<(), Error<E>> {
-        if include {
+        self.set_anticommon(ch, include).await
+    }
+
+    /// Includes or excludes the given channel from the Anti-Common Button
+    /// algorithm.
+    ///
+    /// Only the ANTICOM bit of the given channel is changed, the ANTIDFORM bits
+    /// and the bits of the other channels are preserved.
+    pub async fn set_anticommon<T: ChannelRegisters>(
+        &mut self,
+        _ch: T,
+        enable: bool,
+    ) -> Result<(), Error<E>> {
+        if enable {
             self.set_register_bits(Register::CommonDeform, T::ANTICOM_BIT)
                 .await
         } else {
@@ -545,12 +1676,27 @@ where
 
     /// Configures whether to include or exclude the given channel
     /// from the Anti-Deform Button algorithm.
+    ///
+    /// Equivalent to [`Self::set_antideform`].
     pub async fn include_channel_in_antideform_algorithm<T: ChannelRegisters>(
         &mut self,
-        _ch: T,
+        ch: T,
         include: bool,
     ) -> Result<(), Error<E>> {
-        if include {
+        self.set_antideform(ch, include).await
+    }
+
+    /// Includes or excludes the given channel from the Anti-Deform Button
+    /// algorithm.
+    ///
+    /// Only the ANTIDFORM bit of the given channel is changed, the ANTICOM bits
+    /// and the bits of the other channels are preserved.
+    pub async fn set_antideform<T: ChannelRegisters>(
+        &mut self,
+        _ch: T,
+        enable: bool,
+    ) -> Result<(), Error<E>> {
+        if enable {
             self.set_register_bits(Register::CommonDeform, T::ANTIDFORM_BIT)
                 .await
         } else {
@@ -559,6 +1705,30 @@ where
         }
     }
 
+    /// Sets which channels are included in the Anti-Common Button algorithm.
+    ///
+    /// Channels not in `channels` are excluded from the algorithm.
+    pub async fn set_anticommon_group(
+        &mut self,
+        channels: impl Into<ChannelSet>,
+    ) -> Result<(), Error<E>> {
+        let bits = channels.into().bits();
+        self.modify_field(Register::CommonDeform, ANTICOM_GROUP, bits)
+            .await
+    }
+
+    /// Sets which channels are included in the Anti-Deform Button algorithm.
+    ///
+    /// Channels not in `channels` are excluded from the algorithm.
+    pub async fn set_antideform_group(
+        &mut self,
+        channels: impl Into<ChannelSet>,
+    ) -> Result<(), Error<E>> {
+        let bits = channels.into().bits();
+        self.modify_field(Register::CommonDeform, ANTIDFORM_GROUP, bits)
+            .await
+    }
+
     /// Sets the output polarity of the given channel.
     pub async fn set_output_polarity<T: ChannelRegisters>(
         &mut self,
@@ -596,16 +1766,16 @@ where
     }
 
     /// Sets the counter scale for the given channel.
+    ///
+    /// All channels share the CNTSC register, so it is read, modified and
+    /// written back with only the two bits of the given channel changed.
     pub async fn set_counter_scale<T: ChannelRegisters>(
         &mut self,
         _ch: T,
         scale: CounterScale,
     ) -> Result<(), Error<E>> {
-        self.modify_register(Register::Cntsc, |mut v| {
-            v &= !T::CNTSC_MASK;
-            v | (scale as u8) << T::CNTSC_OFFSET
-        })
-        .await
+        self.modify_field(Register::Cntsc, cntsc_field::<T>(), scale as u8)
+            .await
     }
 
     /// Sets the sensor configuration for the given channel.
@@ -614,77 +1784,150 @@ where
         ch: T,
         config: &SensorConfig,
     ) -> Result<(), Error<E>> {
-        if config.cycle_count >= 0x20 {
-            return Err(Error::InvalidParameter);
-        }
-
-        let mut value = config.cycle_count;
+        let mut value = SENCY
+            .set(0, config.cycle_count)
+            .ok_or(Error::InvalidParameter)?;
         value |= config.rp_range as u8;
         value |= config.frequency_range as u8;
 
         self.write_register(ch.sensor_config(), value).await
     }
 
+    /// Sets the sensor Rp range for the given channel,
+    /// leaving the rest of its sensor configuration untouched.
+    pub async fn set_rp_range<T: ChannelRegisters>(
+        &mut self,
+        ch: T,
+        range: RpRange,
+    ) -> Result<(), Error<E>> {
+        self.modify_field(ch.sensor_config(), RP_RANGE, RP_RANGE.get(range as u8))
+            .await
+    }
+
+    /// Sets the sensor frequency range for the given channel,
+    /// leaving the rest of its sensor configuration untouched.
+    pub async fn set_frequency_range<T: ChannelRegisters>(
+        &mut self,
+        ch: T,
+        range: FrequencyRange,
+    ) -> Result<(), Error<E>> {
+        self.modify_field(ch.sensor_config(), FREQ, FREQ.get(range as u8))
+            .await
+    }
+
     /// Sets the Fast Tracking Factor (FTF) for the given channel.
+    ///
+    /// Channels 1 and 2 share the FTF1_2 register, so it is read, modified and
+    /// written back with only the bits of the given channel changed. The same
+    /// applies to the other bits of FTF0 and FTF3.
     pub async fn set_fast_tracking_factor<T: ChannelRegisters>(
         &mut self,
         ch: T,
         ftf: FastTrackingFactor,
     ) -> Result<(), Error<E>> {
-        self.modify_register(ch.ftf(), |mut v| {
-            v &= !T::FTF_MASK;
-            v | (ftf as u8) << T::FTF_OFFSET
+        self.modify_field(ch.ftf(), ftf_field::<T>(), ftf as u8)
+            .await
+    }
+
+    /// Sets the Fast Tracking Factor (FTF) of all channels, indexed by
+    /// channel number.
+    ///
+    /// FTF1_2 is composed from channels 1 and 2 and written at once, so only
+    /// three registers are written. The bits outside the FTF fields of all
+    /// three registers, including the reserved low nibble of FTF1_2, are
+    /// preserved.
+    pub async fn set_all_ftf(&mut self, ftf: [FastTrackingFactor; 4]) -> Result<(), Error<E>> {
+        let ftf1_2 = ((ftf[1] as u8) << FTF1_OFFSET) | ((ftf[2] as u8) << FTF2_OFFSET);
+        let ftf0 = ftf_field::<Channel0>();
+        let ftf3 = ftf_field::<Channel3>();
+        self.modify_field(Register::Ftf0, ftf0, ftf[0] as u8)
+            .await?;
+        self.modify_register(Register::Ftf1_2, |value| {
+            (value & !(FTF1_MASK | FTF2_MASK)) | ftf1_2
         })
-        .await
+        .await?;
+        self.modify_field(Register::Ftf3, ftf3, ftf[3] as u8).await
     }
+}
 
-    /// Writes a value to a given register.
-    pub async fn write_register(&mut self, register: Register, value: u8) -> Result<(), Error<E>> {
-        if register.is_read_only() {
-            return Err(Error::WriteToReadOnly);
-        }
+#[cfg(test)]
+mod tests {
+    use crate::mock::{Call, CallLog, MockDelay, MockI2c, MockPin, NoDelay, block_on};
+    use crate::*;
 
-        self.i2c
-            .write(I2C_ADDR, &[register.addr(), value])
-            .await
-            .map_err(Error::I2c)?;
-        Ok(())
-    }
+    type Session<'a> = Ldc3114<&'a mut MockI2c, Config>;
 
-    /// Reads a value from a given register.
-    pub async fn read_register(&mut self, register: Register) -> Result<u8, Error<E>> {
-        let mut buffer = [0u8; 1];
-        self.i2c
-            .write_read(I2C_ADDR, &[register.addr()], &mut buffer)
-            .await
-            .map_err(Error::I2c)?;
-        Ok(buffer[0])
+    #[test]
+    fn with_config_session_restores_normal_mode_after_ok() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::Gain0, 0xC0);
+        let mut ldc = Ldc3114::new(i2c);
+
+        let result = block_on(
+            ldc.with_config_session(&mut NoDelay, async |ldc: &mut Session| {
+                ldc.set_gain(Channel0, 0x15).await?;
+                Ok(42)
+            }),
+        );
+
+        assert!(matches!(result, Ok(42)));
+        assert_eq!(ldc.i2c.get(Register::Gain0), 0xD5);
+        let reset = Register::Reset.addr();
+        assert_eq!(ldc.i2c.writes.first(), Some(&(reset, CONFIG_MODE)));
+        assert_eq!(ldc.i2c.writes.last(), Some(&(reset, 0)));
     }
 
-    /// Modifies the value of a given register.
-    pub async fn modify_register<F>(&mut self, register: Register, f: F) -> Result<(), Error<E>>
-    where
-        F: FnOnce(u8) -> u8,
-    {
-        let value = self.read_register(register).await?;
-        self.write_register(register, f(value)).await
+    #[test]
+    fn with_config_session_restores_normal_mode_after_err() {
+        let mut ldc = Ldc3114::new(MockI2c::new());
+
+        let result = block_on(
+            ldc.with_config_session(&mut NoDelay, async |ldc: &mut Session| {
+                ldc.set_gain(Channel0, 0x40).await
+            }),
+        );
+
+        assert!(matches!(result, Err(Error::InvalidParameter)));
+        let reset = Register::Reset.addr();
+        assert_eq!(ldc.i2c.writes, [(reset, CONFIG_MODE), (reset, 0)]);
     }
 
-    /// Sets some bits of a given register.
-    pub async fn set_register_bits(
-        &mut self,
-        register: Register,
-        bits: u8,
-    ) -> Result<(), Error<E>> {
-        self.modify_register(register, |v| v | bits).await
+    #[test]
+    fn with_config_session_restores_normal_mode_after_timeout() {
+        let mut i2c = MockI2c::new();
+        i2c.ready_to_write = false;
+        let mut ldc = Ldc3114::new(i2c);
+        let mut ran = false;
+
+        let result = block_on(
+            ldc.with_config_session(&mut NoDelay, async |_: &mut Session| {
+                ran = true;
+                Ok(())
+            }),
+        );
+
+        assert!(matches!(result, Err(Error::Timeout)));
+        assert!(!ran);
+        let reset = Register::Reset.addr();
+        assert_eq!(ldc.i2c.writes, [(reset, CONFIG_MODE), (reset, 0)]);
     }
 
-    /// Clears some bits of a given register.
-    pub async fn clear_register_bits(
-        &mut self,
-        register: Register,
-        bits: u8,
-    ) -> Result<(), Error<E>> {
-        self.modify_register(register, |v| v & !bits).await
+    #[test]
+    fn hardware_reset_power_cycles_then_polls_until_ready() {
+        let log = CallLog::default();
+        let mut i2c = MockI2c::new();
+        i2c.nacks = 1;
+        let mut ldc = Ldc3114::new_with_reset(i2c, MockPin(log.clone()));
+
+        block_on(ldc.hardware_reset(&mut MockDelay(log.clone()), 20, 3)).unwrap();
+
+        let expected = [
+            Call::Low,
+            Call::DelayMs(20),
+            Call::High,
+            Call::DelayMs(3),
+            Call::DelayUs(POLL_INTERVAL_US),
+        ];
+        assert_eq!(*log.borrow(), expected);
     }
 }
@@ -0,0 +1,124 @@
+/// Integer moving average over the last `N` samples of a channel.
+///
+/// Feed it the processed data of a channel, e.g. from `read_button_data`,
+/// and read back the smoothed value. Use one instance per channel.
+///
+/// Until the window is full, the average is taken over the samples pushed so
+/// far, so the first sample is returned as is.
+///
+/// ```
+/// use ldc3114::MovingAverage;
+///
+/// let mut filter = MovingAverage::<4>::new();
+/// assert_eq!(filter.push(100), 100);
+/// assert_eq!(filter.push(200), 150);
+/// ```
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MovingAverage<const N: usize> {
+    samples: [i16; N],
+    len: usize,
+    next: usize,
+    sum: i64,
+}
+
+impl<const N: usize> MovingAverage<N> {
+    /// Creates an empty moving average.
+    pub const fn new() -> Self {
+        const { assert!(N > 0, "window must hold at least one sample") };
+        Self {
+            samples: [0; N],
+            len: 0,
+            next: 0,
+            sum: 0,
+        }
+    }
+
+    /// Adds a sample, evicting the oldest one once the window is full,
+    /// and returns the updated average.
+    pub fn push(&mut self, sample: i16) -> i16 {
+        if self.len == N {
+            self.sum -= self.samples[self.next] as i64;
+        } else {
+            self.len += 1;
+        }
+        self.samples[self.next] = sample;
+        self.sum += sample as i64;
+        self.next = (self.next + 1) % N;
+        self.average()
+    }
+
+    /// Average of the samples in the window, or 0 if it is empty.
+    pub fn average(&self) -> i16 {
+        if self.len == 0 {
+            return 0;
+        }
+        (self.sum / self.len as i64) as i16
+    }
+
+    /// Removes all samples from the window.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl<const N: usize> Default for MovingAverage<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converges_to_a_constant_input() {
+        let mut filter = MovingAverage::<8>::new();
+        for _ in 0..20 {
+            assert_eq!(filter.push(-300), -300);
+        }
+    }
+
+    #[test]
+    fn window_fill_isnt_diluted_by_empty_slots() {
+        let mut filter = MovingAverage::<4>::new();
+        assert_eq!(filter.average(), 0);
+        assert_eq!(filter.push(10), 10);
+        assert_eq!(filter.push(20), 15);
+        assert_eq!(filter.push(30), 20);
+        assert_eq!(filter.push(40), 25);
+    }
+
+    #[test]
+    fn tracks_a_step_within_one_window() {
+        let mut filter = MovingAverage::<4>::new();
+        for _ in 0..4 {
+            filter.push(0);
+        }
+
+        let step = [100, 100, 100, 100, 100].map(|sample| filter.push(sample));
+        assert_eq!(step, [25, 50, 75, 100, 100]);
+    }
+
+    #[test]
+    fn full_scale_samples_dont_overflow() {
+        let mut filter = MovingAverage::<16>::new();
+        for _ in 0..32 {
+            assert_eq!(filter.push(i16::MIN), i16::MIN);
+        }
+        for _ in 0..16 {
+            filter.push(i16::MAX);
+        }
+        assert_eq!(filter.average(), i16::MAX);
+    }
+
+    #[test]
+    fn reset_empties_the_window() {
+        let mut filter = MovingAverage::<4>::new();
+        filter.push(1000);
+        filter.reset();
+        assert_eq!(filter.average(), 0);
+        assert_eq!(filter.push(-8), -8);
+    }
+}
@@ -4,22 +4,406 @@
 
 #[cfg(feature = "async")]
 mod asynch;
+#[cfg(feature = "filter")]
+mod filter;
+#[cfg(feature = "filter")]
+pub use filter::*;
+#[cfg(test)]
+mod mock;
 mod register;
 pub use register::*;
 #[cfg(not(feature = "async"))]
 mod sync;
 
+use core::marker::PhantomData;
+
 /// LDC3114 has a fixed I2C address of 0x2A.
 const I2C_ADDR: u8 = 0x2A;
 
+/// Manufacturer ID of Texas Instruments.
+pub const MANUFACTURER_ID: u16 = 0x5449;
+
+/// Device ID of the LDC3114.
+pub const DEVICE_ID: u16 = 0x1000;
+
+/// Manufacturer and device IDs packed as returned by `full_id`, with the
+/// manufacturer ID in the high half.
+///
+/// ```
+/// assert_eq!(ldc3114::EXPECTED_FULL_ID, 0x5449_1000);
+/// ```
+pub const EXPECTED_FULL_ID: u32 = ((MANUFACTURER_ID as u32) << 16) | DEVICE_ID as u32;
+
+/// Length of the contiguous block of configuration registers from EN (0x0C)
+/// to CNTSC (0x1E).
+pub const CONFIG_BLOCK_LEN: usize = 0x13;
+
+/// Maximum sensor frequency after division by the LC divider, in Hz.
+///
+/// `LCDIV` must be chosen so that `fSENSOR / 2^LCDIV` stays below this limit
+/// for the highest frequency of every enabled channel's frequency range.
+pub const MAX_DIVIDED_SENSOR_FREQUENCY_HZ: u32 = 4_000_000;
+
+/// Maximum time to wait for new data in microseconds, longer than the
+/// period of the slowest scan rate (0.625 SPS in low power mode).
+const DATA_READY_TIMEOUT_US: u32 = 2_000_000;
+
+/// Maximum number of status polls when waiting for the device.
+const MAX_POLLS: u32 = 100;
+
+/// Delay between status polls in microseconds.
+const POLL_INTERVAL_US: u32 = 1_000;
+
+/// Frequency of the internal reference clock in Hz.
+const REFERENCE_CLOCK_HZ: u32 = 44_000_000;
+
+/// Sign-extends the 12-bit processed button data held in a DATA register pair.
+fn button_data_from_bytes(lsb: u8, msb: u8) -> i16 {
+    (i16::from_le_bytes([lsb, msb]) << 4) >> 4
+}
+
+/// Returns the gain register value whose amplification is closest to `factor`.
+///
+/// A gain register value `GAIN` amplifies the signal by a factor of
+/// `2^(GAIN / 8)`, i.e. from 1 for `GAIN = 0` up to about 235 for
+/// `GAIN = 63`. Factors outside of this range, including infinities, are
+/// clamped to it. Returns `None` if `factor` is NaN, which has no closest
+/// gain. This is only available with the `float` feature.
+///
+/// ```
+/// # #[cfg(feature = "float")]
+/// # {
+/// use ldc3114::gain_from_sensitivity;
+///
+/// assert_eq!(gain_from_sensitivity(2.0), Some(8));
+/// assert_eq!(gain_from_sensitivity(f32::INFINITY), Some(0x3F));
+/// assert_eq!(gain_from_sensitivity(f32::NAN), None);
+/// # }
+/// ```
+#[cfg(feature = "float")]
+pub fn gain_from_sensitivity(factor: f32) -> Option<u8> {
+    if factor.is_nan() {
+        return None;
+    }
+    // 2^(1/8)
+    const STEP: f32 = 1.090_507_7;
+    // Midpoint (in the logarithmic domain) between the current and next code.
+    let mut threshold = 1.044_273_8;
+    for gain in 0..0x3F {
+        if factor < threshold {
+            return Some(gain);
+        }
+        threshold *= STEP;
+    }
+    Some(0x3F)
+}
+
+/// Returns the amplification of a gain register value in milli-decibels.
+///
+/// A factor of `2^(GAIN / 8)` corresponds to `GAIN * 20 * log10(2) / 8`, i.e.
+/// about 0.7526 dB per code. Values above 63 are clamped to 63.
+///
+/// ```
+/// assert_eq!(ldc3114::gain_to_milli_db(0), 0);
+/// assert_eq!(ldc3114::gain_to_milli_db(0x28), 30_103);
+/// assert_eq!(ldc3114::gain_to_milli_db(0xFF), ldc3114::gain_to_milli_db(63));
+/// ```
+pub const fn gain_to_milli_db(gain: u8) -> u32 {
+    let gain = if gain > 0x3F { 0x3F } else { gain };
+    // 20 * log10(2) / 8 dB in units of 0.1 micro-decibels.
+    (gain as u32 * 7_525_750 + 5_000) / 10_000
+}
+
+/// Returns the value of the given register from a configuration block read
+/// with `read_raw_config_block`.
+///
+/// Returns `None` if the register is not part of the block.
+///
+/// ```
+/// use ldc3114::{CONFIG_BLOCK_LEN, Register, config_block_value};
+///
+/// let mut block = [0; CONFIG_BLOCK_LEN];
+/// block[1] = 0x03;
+/// assert_eq!(config_block_value(&block, Register::NpScanRate), Some(0x03));
+/// assert_eq!(config_block_value(&block, Register::Sensor0Config), None);
+/// ```
+pub fn config_block_value(block: &[u8; CONFIG_BLOCK_LEN], register: Register) -> Option<u8> {
+    let offset = register.addr().checked_sub(Register::En.addr())?;
+    block.get(offset as usize).copied()
+}
+
+/// Checks if a bus error is a missing acknowledge.
+///
+/// Taking the error as the associated type of the bus, whose bound is
+/// implied, keeps the driver impls free of an extra bound on their error
+/// type.
+fn is_no_acknowledge<I2C: embedded_hal::i2c::ErrorType>(error: &I2C::Error) -> bool {
+    matches!(
+        embedded_hal::i2c::Error::kind(error),
+        embedded_hal::i2c::ErrorKind::NoAcknowledge(_)
+    )
+}
+
+/// Assembles the 24-bit raw data from the RAW_DATA registers of a channel,
+/// in the order they are read, i.e. from RAW_DATAn_3 (MSB) to RAW_DATAn_1
+/// (LSB).
+///
+/// The top byte of the returned value is always zero.
+fn raw_data_from_bytes(bytes: [u8; 3]) -> u32 {
+    (u32::from(bytes[0]) << 16) | (u32::from(bytes[1]) << 8) | u32::from(bytes[2])
+}
+
+/// Applies a software press threshold with hysteresis to processed data.
+///
+/// A released channel is pressed once `data` reaches `threshold`, and a
+/// pressed channel is released once `data` drops below
+/// `threshold - hysteresis`.
+fn press_state(pressed: bool, data: i16, threshold: i16, hysteresis: i16) -> bool {
+    if pressed {
+        data >= threshold.saturating_sub(hysteresis)
+    } else {
+        data >= threshold
+    }
+}
+
+/// Checks IDs packed as returned by `full_id` against [`EXPECTED_FULL_ID`].
+fn check_full_id<E>(id: u32) -> Result<(), Error<E>> {
+    if id != EXPECTED_FULL_ID {
+        return Err(Error::UnexpectedDevice {
+            manufacturer_id: (id >> 16) as u16,
+            device_id: id as u16,
+        });
+    }
+    Ok(())
+}
+
+/// Supply enable pin of a driver created without one.
+pub struct NoReset;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Operating mode of the device, tracked in the type of the driver.
+///
+/// This trait is sealed, [`Normal`] and [`Config`] are the only modes.
+pub trait Mode: sealed::Sealed {
+    /// Whether configuration registers can be written in this mode.
+    #[doc(hidden)]
+    const CONFIG: bool;
+}
+
+/// Normal mode, in which the device converts and configuration registers
+/// are read-only.
+pub struct Normal;
+
+/// Configuration mode, in which conversions are halted and configuration
+/// registers can be written.
+pub struct Config;
+
+impl sealed::Sealed for Normal {}
+impl sealed::Sealed for Config {}
+
+impl Mode for Normal {
+    const CONFIG: bool = false;
+}
+
+impl Mode for Config {
+    const CONFIG: bool = true;
+}
+
+/// Error of a mode transition, handing back the driver in the mode it was
+/// in before the transition.
+///
+/// Converts into the underlying [`Error`] with `?` when the driver isn't
+/// needed anymore.
+pub struct TransitionError<LDC, E> {
+    /// Driver in the mode it was in before the transition.
+    pub ldc: LDC,
+    /// Error that made the transition fail.
+    pub error: Error<E>,
+}
+
+impl<LDC, E: core::fmt::Debug> core::fmt::Debug for TransitionError<LDC, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TransitionError")
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<LDC, E> From<TransitionError<LDC, E>> for Error<E> {
+    fn from(e: TransitionError<LDC, E>) -> Self {
+        e.error
+    }
+}
+
 /// Driver for the LDC3114.
-pub struct Ldc3114<I2C> {
+///
+/// `MODE` is the operating mode the device is in, see [`Mode`]. Drivers are
+/// created in [`Normal`] mode, and the setters of configuration registers
+/// are only available in [`Config`] mode, which makes a missing mode switch
+/// a compile error. `RST` is the supply enable pin owned by a driver created
+/// with `new_with_reset`, or [`NoReset`] otherwise.
+pub struct Ldc3114<I2C, MODE = Normal, RST = NoReset> {
     i2c: I2C,
+    reset: RST,
+    mode: PhantomData<MODE>,
+    state: State,
+}
+
+/// Cached state of the driver, carried over between modes.
+#[derive(Clone, Copy)]
+struct State {
     sency0: u8,
     sency1: u8,
     sency2: u8,
     sency3: u8,
     lcdiv: u8,
+    scan_rate: u8,
+    cache_valid: bool,
+    ever_ready: bool,
+    status: StatusAccumulator,
+    press_states: u8,
+}
+
+impl<I2C, MODE, RST> Ldc3114<I2C, MODE, RST> {
+    /// Creates a driver instance for a device fresh out of reset.
+    fn from_parts(i2c: I2C, reset: RST) -> Self {
+        Self {
+            i2c,
+            reset,
+            mode: PhantomData,
+            state: State {
+                sency0: 4,
+                sency1: 4,
+                sency2: 4,
+                sency3: 4,
+                lcdiv: 3,
+                scan_rate: ScanRate::Medium as u8,
+                cache_valid: true,
+                ever_ready: false,
+                status: StatusAccumulator::new(),
+                press_states: 0,
+            },
+        }
+    }
+
+    /// Converts the driver to another mode without touching the device.
+    fn into_mode<M>(self) -> Ldc3114<I2C, M, RST> {
+        Ldc3114 {
+            i2c: self.i2c,
+            reset: self.reset,
+            mode: PhantomData,
+            state: self.state,
+        }
+    }
+
+    /// Borrows the underlying I2C bus for a custom transaction.
+    ///
+    /// Transactions addressed to the LDC3114 bypass the driver and may leave
+    /// the device in a state that the driver doesn't expect. If the device
+    /// configuration is modified, call `refresh_cache` afterwards.
+    pub fn i2c_mut(&mut self) -> &mut I2C {
+        &mut self.i2c
+    }
+
+    /// Checks if any status read since construction reported
+    /// STATUS:CHIP_READY.
+    ///
+    /// Unlike the momentary CHIP_READY bit, this flag stays set once the chip
+    /// has been seen ready.
+    pub fn has_been_ready(&self) -> bool {
+        self.state.ever_ready
+    }
+
+    /// Returns the status accumulated over every status register read made
+    /// by the driver since the last [`Self::take_status`].
+    ///
+    /// Clear-on-read flags consumed by any read, e.g. while polling for
+    /// readiness, are kept here until taken.
+    pub fn accumulated_status(&self) -> Status {
+        self.state.status.status()
+    }
+
+    /// Returns the accumulated status like [`Self::accumulated_status`] and
+    /// clears the accumulated flags.
+    pub fn take_status(&mut self) -> Status {
+        self.state.status.take()
+    }
+
+    /// Records the flags of a status register read.
+    fn latch_status(&mut self, sr: u8) {
+        self.state.ever_ready |= sr & CHIP_READY != 0;
+        self.state.status.accumulate(sr);
+    }
+
+    /// Restores the cached state to that of a device fresh out of reset.
+    fn reset_state(&mut self) {
+        self.state.sency0 = 4;
+        self.state.sency1 = 4;
+        self.state.sency2 = 4;
+        self.state.sency3 = 4;
+        self.state.lcdiv = 3;
+        self.state.scan_rate = ScanRate::Medium as u8;
+        self.state.cache_valid = true;
+    }
+
+    /// Computes the sensor frequency in Hz from raw data of the given channel,
+    /// using the cached SENCY and LCDIV values.
+    ///
+    /// `clock_hz` is the reference clock frequency the raw data was counted
+    /// against, nominally 44 MHz for the internal oscillator. The relationship
+    /// is the one documented for `read_sensor_frequency`, evaluated with
+    /// integer arithmetic only. Results that don't fit in a `u32` saturate.
+    ///
+    /// Returns `None` if the cached sensor configuration is unknown, and
+    /// `Some(0)` if `raw` is zero.
+    pub fn sensor_frequency_hz<T: ChannelRegisters>(
+        &self,
+        _ch: T,
+        raw: u32,
+        clock_hz: u32,
+    ) -> Option<u32> {
+        if !self.state.cache_valid {
+            return None;
+        }
+        if raw == 0 {
+            return Some(0);
+        }
+
+        let sency = match T::CH {
+            0 => self.state.sency0,
+            1 => self.state.sency1,
+            2 => self.state.sency2,
+            3 => self.state.sency3,
+            _ => unreachable!(),
+        };
+
+        let w = 128 * (1 + sency as u64) * (1 << self.state.lcdiv as u64);
+        let fsensor = 30 * w * clock_hz as u64 / raw as u64;
+        Some(u32::try_from(fsensor).unwrap_or(u32::MAX))
+    }
+
+    /// Returns the cached scan rate in normal power mode.
+    ///
+    /// Returns `None` if the cached configuration is unknown or the
+    /// NP_SCAN_RATE field holds a reserved encoding.
+    pub fn scan_rate(&self) -> Option<ScanRate> {
+        if !self.state.cache_valid {
+            return None;
+        }
+        ScanRate::try_from(self.state.scan_rate).ok()
+    }
+
+    /// Marks the cached sensor configuration as unknown without touching
+    /// the device, e.g. after it was reset by other means.
+    ///
+    /// Methods relying on the cached values return
+    /// [`Error::CacheInvalidated`] until the cache is refreshed.
+    pub fn invalidate_cache(&mut self) {
+        self.state.cache_valid = false;
+    }
 }
 
 /// Error type.
@@ -32,6 +416,81 @@ pub enum Error<I2cError> {
     WriteToReadOnly,
     /// Invalid parameter.
     InvalidParameter,
+    /// The configuration read back from the device does not match
+    /// the one that was written.
+    Verification,
+    /// The device did not become ready within the polling budget.
+    ///
+    /// The device acknowledged every transfer, but the polled flag didn't
+    /// assert, e.g. STATUS:CHIP_READY after [`Ldc3114::reset`] or
+    /// STATUS:RDY_TO_WRITE when entering configuration mode. This indicates a
+    /// present but stuck or busy chip, as opposed to [`Error::I2c`] or
+    /// [`Error::NotPresent`] for bus faults. Every polling helper reports
+    /// this variant, none of them remaps it.
+    Timeout,
+    /// The cached sensor configuration is unknown and must be refreshed.
+    CacheInvalidated,
+    /// Attempted to write to a register that can only be written in
+    /// configuration mode while the driver is not in it.
+    ///
+    /// The driver detects this before any transfer, so unlike
+    /// [`Error::Timeout`] it says nothing about the state of the device.
+    NotReadyToWrite,
+    /// Setting the state of a GPIO pin failed.
+    Pin,
+    /// No device acknowledged the LDC3114 address.
+    NotPresent,
+    /// The device at the LDC3114 address is not an LDC3114, e.g. because
+    /// another device on the bus uses the same address.
+    UnexpectedDevice {
+        /// Manufacturer ID read from the device.
+        manufacturer_id: u16,
+        /// Device ID read from the device.
+        device_id: u16,
+    },
+    /// The configuration has an invalid field or is inconsistent.
+    InvalidConfig(ConfigError),
+    /// A register holds a value that doesn't encode any variant of the
+    /// corresponding enum.
+    InvalidEncoding(InvalidEncoding),
+    /// Two consecutive reads of the device IDs disagreed, which indicates
+    /// an unreliable bus rather than an unexpected device.
+    UnstableRead,
+}
+
+/// Invalid field or inconsistency found in a [`DeviceConfig`].
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ConfigError {
+    /// The gain of the channel doesn't fit in 6 bits.
+    GainOutOfRange {
+        /// Channel number.
+        channel: u8,
+    },
+    /// The sensor cycle count of the channel doesn't fit in 5 bits.
+    CycleCountOutOfRange {
+        /// Channel number.
+        channel: u8,
+    },
+    /// The baseline tracking increment in normal power mode doesn't fit in
+    /// 3 bits.
+    BaselineTrackingIncrementNpOutOfRange,
+    /// The baseline tracking increment in low power mode doesn't fit in
+    /// 3 bits.
+    BaselineTrackingIncrementLpOutOfRange,
+    /// The LC divider doesn't fit in 3 bits.
+    LcDividerOutOfRange,
+    /// The hysteresis doesn't fit in 4 bits.
+    HysteresisOutOfRange,
+    /// The anti-twist threshold doesn't fit in 3 bits.
+    AntitwistOutOfRange,
+    /// The LC divider is too small for the frequency range of the channel,
+    /// so the divided sensor frequency may exceed
+    /// [`MAX_DIVIDED_SENSOR_FREQUENCY_HZ`].
+    LcDividerIncompatible {
+        /// Channel number.
+        channel: u8,
+    },
 }
 
 /// Status flags.
@@ -63,6 +522,66 @@ pub struct Status {
     pub register_integrity_bad: bool,
 }
 
+impl Status {
+    /// Decodes the value of the STATUS register.
+    pub(crate) fn from_bits(sr: u8) -> Self {
+        Self {
+            output_status: (sr & OUT_STATUS != 0),
+            chip_ready: (sr & CHIP_READY != 0),
+            ready_to_write: (sr & RDY_TO_WRITE != 0),
+            maximum_output_code: (sr & MAXOUT != 0),
+            fsm_watchdog_error: (sr & FSM_WD != 0),
+            lc_sensor_watchdog_error: (sr & LC_WD != 0),
+            button_timeout: (sr & TIMEOUT != 0),
+            register_integrity_bad: (sr & REGISTER_FLAG != 0),
+        }
+    }
+}
+
+/// Accumulator of status flags across status register reads.
+///
+/// Reading the status register clears all of its clear-on-read flags at
+/// once. Feeding every read into an accumulator keeps flags that were
+/// cleared by a read made for another purpose until they are taken. The
+/// driver keeps one fed by all of its own status reads, see
+/// [`Ldc3114::accumulated_status`].
+#[derive(Clone, Copy, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct StatusAccumulator(u8);
+
+impl StatusAccumulator {
+    /// Flags of the status register that are cleared on read.
+    const CLEAR_ON_READ: u8 = OUT_STATUS | MAXOUT | FSM_WD | LC_WD | TIMEOUT | REGISTER_FLAG;
+
+    /// Creates an empty accumulator.
+    pub const fn new() -> Self {
+        Self(0)
+    }
+
+    /// Merges the value of a status register read.
+    ///
+    /// Clear-on-read flags are accumulated, while CHIP_READY and
+    /// RDY_TO_WRITE reflect the latest read. Status reads made by the driver
+    /// are also accumulated in the driver, see
+    /// [`Ldc3114::accumulated_status`], so this is only needed for values
+    /// read by other means, e.g. through [`Ldc3114::i2c_mut`].
+    pub fn accumulate(&mut self, sr: u8) {
+        self.0 = (self.0 & Self::CLEAR_ON_READ) | sr;
+    }
+
+    /// Returns the accumulated status.
+    pub fn status(&self) -> Status {
+        Status::from_bits(self.0)
+    }
+
+    /// Returns the accumulated status and clears the accumulated flags.
+    pub fn take(&mut self) -> Status {
+        let status = self.status();
+        self.0 &= !Self::CLEAR_ON_READ;
+        status
+    }
+}
+
 /// Channel output logic states.
 #[derive(Clone, Copy)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -80,6 +599,584 @@ pub struct OutputLogicStates {
     pub out3: bool,
 }
 
+impl OutputLogicStates {
+    /// Decodes the value of the OUT register.
+    pub(crate) fn from_bits(out: u8) -> Self {
+        Self {
+            new_data_available: (out & DATA_RDY != 0),
+            out0: (out & OUT0 != 0),
+            out1: (out & OUT1 != 0),
+            out2: (out & OUT2 != 0),
+            out3: (out & OUT3 != 0),
+        }
+    }
+
+    /// Returns the button output logic states indexed by channel number.
+    pub const fn outputs(&self) -> [bool; 4] {
+        [self.out0, self.out1, self.out2, self.out3]
+    }
+
+    /// Returns which button outputs were asserted since the `prev` snapshot.
+    pub fn rising(&self, prev: &Self) -> [bool; 4] {
+        let (now, prev) = (self.outputs(), prev.outputs());
+        core::array::from_fn(|ch| now[ch] && !prev[ch])
+    }
+
+    /// Returns which button outputs were deasserted since the `prev` snapshot.
+    pub fn falling(&self, prev: &Self) -> [bool; 4] {
+        let (now, prev) = (self.outputs(), prev.outputs());
+        core::array::from_fn(|ch| !now[ch] && prev[ch])
+    }
+}
+
+/// Raw STATUS and OUT register values captured in a single read.
+///
+/// An event is two bytes that can be cheaply queued, e.g. from an interrupt
+/// handler, and decoded later in the main loop.
+///
+/// ```
+/// use ldc3114::Event;
+///
+/// // Queue the raw bytes of an event...
+/// let queued = Event::from_bytes([0x40, 0x12]).to_bytes();
+///
+/// // ...and decode them later.
+/// let event = Event::from_bytes(queued);
+/// assert!(event.status().chip_ready);
+/// assert!(event.outputs().new_data_available);
+/// assert_eq!(event.outputs().outputs(), [false, true, false, false]);
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Event {
+    status: u8,
+    out: u8,
+}
+
+impl Event {
+    /// Creates an event from the STATUS and OUT register values, in that order.
+    pub const fn from_bytes(bytes: [u8; 2]) -> Self {
+        Self {
+            status: bytes[0],
+            out: bytes[1],
+        }
+    }
+
+    /// Returns the STATUS and OUT register values, in that order.
+    pub const fn to_bytes(&self) -> [u8; 2] {
+        [self.status, self.out]
+    }
+
+    /// Decodes the status flags.
+    pub fn status(&self) -> Status {
+        Status::from_bits(self.status)
+    }
+
+    /// Decodes the channel output logic states.
+    pub fn outputs(&self) -> OutputLogicStates {
+        OutputLogicStates::from_bits(self.out)
+    }
+}
+
+/// Processed data and button state of a channel.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChannelState {
+    /// Processed button algorithm data.
+    pub data: i16,
+    /// Button output logic state.
+    pub pressed: bool,
+}
+
+/// Result of [`Ldc3114::production_bringup`].
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BringupReport {
+    /// Whether the configuration read back from the device matched.
+    pub config_verified: bool,
+    /// Status flags accumulated over the bring-up, up to the channel
+    /// snapshot.
+    pub status: Status,
+    /// Snapshot of all channels, indexed by channel number.
+    pub channels: [ChannelState; 4],
+}
+
+impl BringupReport {
+    /// Checks if the unit passed, i.e. the configuration was verified, the
+    /// chip is ready and no error flag is set.
+    pub fn passed(&self) -> bool {
+        let status = &self.status;
+        self.config_verified
+            && status.chip_ready
+            && !status.maximum_output_code
+            && !status.fsm_watchdog_error
+            && !status.lc_sensor_watchdog_error
+            && !status.button_timeout
+            && !status.register_integrity_bad
+    }
+}
+
+/// Tracker of the time at which new data was last reported by the device.
+///
+/// `T` is a timestamp of any monotonic clock provided by the caller.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DataAgeTracker<T> {
+    last_data_ready: Option<T>,
+}
+
+impl<T: Copy> DataAgeTracker<T> {
+    /// Creates a tracker that hasn't seen new data yet.
+    pub const fn new() -> Self {
+        Self {
+            last_data_ready: None,
+        }
+    }
+
+    /// Timestamp at which new data was last reported.
+    pub fn last_data_ready(&self) -> Option<T> {
+        self.last_data_ready
+    }
+
+    /// Records whether new data was reported at `now` and returns the age of
+    /// the latest data.
+    pub(crate) fn update<D>(&mut self, data_ready: bool, now: T) -> Option<D>
+    where
+        T: core::ops::Sub<Output = D>,
+    {
+        if data_ready {
+            self.last_data_ready = Some(now);
+        }
+        self.last_data_ready.map(|t| now - t)
+    }
+}
+
+impl<T: Copy> Default for DataAgeTracker<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Debounced button states and edges of all channels, indexed by channel
+/// number.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ButtonScan {
+    /// Debounced button states.
+    pub pressed: [bool; 4],
+    /// Buttons that became pressed on this scan.
+    pub pressed_edges: [bool; 4],
+    /// Buttons that became released on this scan.
+    pub released_edges: [bool; 4],
+}
+
+/// Software debouncer of the button outputs of all channels.
+///
+/// A button changes state once its output has differed from the debounced
+/// state for `scans` consecutive scans, so shorter chatter is rejected. Scan
+/// the device with [`Ldc3114::scan_buttons`], which reads the OUT register
+/// once per scan.
+///
+/// ```
+/// use ldc3114::ButtonScanner;
+///
+/// let mut scanner = ButtonScanner::new(2);
+///
+/// // Chatter on channel 0 is rejected.
+/// assert!(!scanner.update([true, false, false, false]).pressed[0]);
+/// assert!(!scanner.update([false, false, false, false]).pressed[0]);
+///
+/// // A press is reported after two consecutive scans.
+/// assert!(!scanner.update([true, false, false, false]).pressed[0]);
+/// let scan = scanner.update([true, false, false, false]);
+/// assert!(scan.pressed[0] && scan.pressed_edges[0]);
+///
+/// // And so is the release.
+/// scanner.update([false; 4]);
+/// let scan = scanner.update([false; 4]);
+/// assert!(!scan.pressed[0] && scan.released_edges[0]);
+/// ```
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ButtonScanner {
+    scans: u8,
+    stable: [bool; 4],
+    counters: [u8; 4],
+}
+
+impl ButtonScanner {
+    /// Creates a scanner with all buttons released that requires `scans`
+    /// consecutive scans to change a button state.
+    pub const fn new(scans: u8) -> Self {
+        Self {
+            scans,
+            stable: [false; 4],
+            counters: [0; 4],
+        }
+    }
+
+    /// Returns the debounced button states.
+    pub const fn pressed(&self) -> [bool; 4] {
+        self.stable
+    }
+
+    /// Updates the debounced states with the raw button outputs of a scan.
+    pub fn update(&mut self, outputs: [bool; 4]) -> ButtonScan {
+        let mut scan = ButtonScan {
+            pressed: self.stable,
+            pressed_edges: [false; 4],
+            released_edges: [false; 4],
+        };
+
+        for (ch, &output) in outputs.iter().enumerate() {
+            if output == self.stable[ch] {
+                self.counters[ch] = 0;
+                continue;
+            }
+
+            self.counters[ch] += 1;
+            if self.counters[ch] >= self.scans {
+                self.counters[ch] = 0;
+                self.stable[ch] = output;
+                scan.pressed[ch] = output;
+                scan.pressed_edges[ch] = output;
+                scan.released_edges[ch] = !output;
+            }
+        }
+        scan
+    }
+}
+
+/// Quick health report of a channel.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChannelHealth {
+    /// The channel is enabled in normal mode.
+    pub enabled: bool,
+    /// The raw data is nonzero, i.e. the LC oscillator is running.
+    pub oscillating: bool,
+    /// The processed data is at its maximum (+0x7FF) or minimum (-0x800) value.
+    pub railed: bool,
+}
+
+impl ChannelHealth {
+    /// Checks if the channel is enabled, oscillating and not railed.
+    pub fn is_healthy(&self) -> bool {
+        self.enabled && self.oscillating && !self.railed
+    }
+}
+
+/// Statistics of a series of processed data samples of a channel.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SignalStats {
+    /// Mean of the samples.
+    pub mean: i16,
+    /// Minimum sample.
+    pub min: i16,
+    /// Maximum sample.
+    pub max: i16,
+}
+
+impl SignalStats {
+    /// Peak-to-peak amplitude of the samples.
+    pub fn peak_to_peak(&self) -> u16 {
+        self.max.abs_diff(self.min)
+    }
+
+    /// Estimates the signal-to-noise ratio of a channel.
+    ///
+    /// `self` must be measured without touching the sensor, `touched` while
+    /// pressing it. The ratio is the shift of the mean over the peak-to-peak
+    /// noise of the untouched measurement, which is infinite if no noise
+    /// was observed, even if the mean didn't shift. This is only available
+    /// with the `float` feature, as it pulls in floating-point arithmetic.
+    #[cfg(feature = "float")]
+    pub fn snr(&self, touched: &SignalStats) -> f32 {
+        let noise = self.peak_to_peak();
+        if noise == 0 {
+            return f32::INFINITY;
+        }
+        let signal = self.mean.abs_diff(touched.mean);
+        signal as f32 / noise as f32
+    }
+}
+
+/// Noise statistics of the processed data of a channel.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NoiseStats {
+    /// Mean of the samples, rounded towards zero.
+    pub mean: i16,
+    /// Minimum sample.
+    pub min: i16,
+    /// Maximum sample.
+    pub max: i16,
+    /// Variance of the samples, rounded towards zero.
+    pub variance: u32,
+}
+
+impl NoiseStats {
+    /// Peak-to-peak amplitude of the samples.
+    pub fn peak_to_peak(&self) -> u16 {
+        self.max.abs_diff(self.min)
+    }
+}
+
+/// Noise measurement of the processed data of all channels, accumulated one
+/// sample at a time.
+///
+/// Unlike [`Ldc3114::measure_signal_stats`], the caller controls the timing of
+/// each sample and may do other work in between. Start with
+/// [`NoiseMeasurement::new`], add samples with
+/// [`Ldc3114::accumulate_noise_sample`] or [`NoiseMeasurement::add_sample`],
+/// and compute the statistics with [`NoiseMeasurement::finish`]. All
+/// arithmetic is integer-only.
+///
+/// ```
+/// use ldc3114::NoiseMeasurement;
+///
+/// let mut measurement = NoiseMeasurement::new();
+/// measurement.add_sample([10, 0, -5, 0]);
+/// measurement.add_sample([14, 0, -7, 0]);
+///
+/// let stats = measurement.finish().unwrap();
+/// assert_eq!((stats[0].mean, stats[0].variance), (12, 4));
+/// assert_eq!(stats[0].peak_to_peak(), 4);
+/// assert_eq!((stats[2].mean, stats[2].variance), (-6, 1));
+/// ```
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NoiseMeasurement {
+    count: u32,
+    sum: [i64; 4],
+    sum_sq: [i64; 4],
+    min: [i16; 4],
+    max: [i16; 4],
+}
+
+impl NoiseMeasurement {
+    /// Begins a noise measurement without any samples.
+    pub const fn new() -> Self {
+        Self {
+            count: 0,
+            sum: [0; 4],
+            sum_sq: [0; 4],
+            min: [i16::MAX; 4],
+            max: [i16::MIN; 4],
+        }
+    }
+
+    /// Adds a sample of the processed data of all channels, indexed by
+    /// channel number.
+    ///
+    /// Once `u32::MAX` samples have been added, further samples are ignored,
+    /// which keeps the sums from overflowing.
+    pub fn add_sample(&mut self, data: [i16; 4]) {
+        if self.count == u32::MAX {
+            return;
+        }
+        self.count += 1;
+        for (ch, &sample) in data.iter().enumerate() {
+            self.sum[ch] += i64::from(sample);
+            self.sum_sq[ch] += i64::from(sample) * i64::from(sample);
+            self.min[ch] = self.min[ch].min(sample);
+            self.max[ch] = self.max[ch].max(sample);
+        }
+    }
+
+    /// Returns the number of samples added so far.
+    pub const fn samples(&self) -> u32 {
+        self.count
+    }
+
+    /// Computes the statistics of each channel, indexed by channel number,
+    /// or `None` if no samples were added.
+    pub fn finish(&self) -> Option<[NoiseStats; 4]> {
+        if self.count == 0 {
+            return None;
+        }
+
+        // The sums fit in an i64 for any sample count, but the products
+        // below don't, so the variance is computed in i128.
+        let n = i128::from(self.count);
+        Some(core::array::from_fn(|ch| {
+            let sum = i128::from(self.sum[ch]);
+            let sum_sq = i128::from(self.sum_sq[ch]);
+            NoiseStats {
+                mean: (sum / n) as i16,
+                min: self.min[ch],
+                max: self.max[ch],
+                variance: ((n * sum_sq - sum * sum) / (n * n)) as u32,
+            }
+        }))
+    }
+}
+
+impl Default for NoiseMeasurement {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Gain register values of all channels.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Gains {
+    /// Gain for channel 0.
+    pub ch0: u8,
+    /// Gain for channel 1.
+    pub ch1: u8,
+    /// Gain for channel 2.
+    pub ch2: u8,
+    /// Gain for channel 3.
+    pub ch3: u8,
+}
+
+impl Gains {
+    /// Returns the gains as an array indexed by channel number.
+    pub const fn as_array(&self) -> [u8; 4] {
+        [self.ch0, self.ch1, self.ch2, self.ch3]
+    }
+}
+
+/// Set of channels.
+///
+/// Bit `n` of the underlying byte represents channel `n`.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChannelSet(u8);
+
+impl ChannelSet {
+    /// Set containing no channels.
+    pub const NONE: Self = Self(0);
+
+    /// Set containing all four channels.
+    pub const ALL: Self = Self(0x0F);
+
+    /// Creates an empty set.
+    pub const fn new() -> Self {
+        Self::NONE
+    }
+
+    /// Returns a copy of the set with the given channel added.
+    pub const fn with<T: ChannelRegisters>(self, _ch: T) -> Self {
+        Self(self.0 | (1 << T::CH))
+    }
+
+    /// Adds the given channel to the set.
+    pub fn insert<T: ChannelRegisters>(&mut self, _ch: T) {
+        self.0 |= 1 << T::CH;
+    }
+
+    /// Removes the given channel from the set.
+    pub fn remove<T: ChannelRegisters>(&mut self, _ch: T) {
+        self.0 &= !(1 << T::CH);
+    }
+
+    /// Checks if the given channel is in the set.
+    pub fn contains<T: ChannelRegisters>(&self, _ch: T) -> bool {
+        self.0 & (1 << T::CH) != 0
+    }
+
+    /// Returns the underlying byte, with bit `n` representing channel `n`.
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Iterates over the channel numbers in the set in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = u8> {
+        let bits = self.0;
+        (0..4).filter(move |ch| bits & (1 << ch) != 0)
+    }
+}
+
+impl From<[bool; 4]> for ChannelSet {
+    fn from(channels: [bool; 4]) -> Self {
+        let bits = channels
+            .iter()
+            .enumerate()
+            .fold(0, |bits, (ch, &included)| bits | ((included as u8) << ch));
+        Self(bits)
+    }
+}
+
+/// Scheduler emulating per-channel scan rates by toggling channel enables.
+///
+/// The LDC3114 has a single scan rate for all channels. To sample some
+/// channels more often than others, each channel is given a period in
+/// scheduler ticks, and on every tick only the channels that are due are
+/// enabled, see [`Ldc3114::scan_scheduled`].
+///
+/// This is an approximation with significant tradeoffs: every change of
+/// the enabled channels goes through configuration mode, which halts
+/// conversions and restarts the oscillators, so the effective rates are
+/// limited by the bus and the settling time of the sensors. Frequent
+/// oscillator restarts also increase power consumption and may cause
+/// interference between neighboring sensors. The button algorithm of a
+/// channel only runs while the channel is enabled, so baseline tracking
+/// slows down for rarely scheduled channels.
+///
+/// ```
+/// use ldc3114::ScheduledScanner;
+///
+/// // Channel 0 on every tick, channel 1 on every other tick,
+/// // channel 2 never and channel 3 on every third tick.
+/// let mut scanner = ScheduledScanner::new([1, 2, 0, 3]);
+/// let due: [u8; 6] = core::array::from_fn(|_| scanner.tick().bits());
+/// assert_eq!(due, [0b1011, 0b0001, 0b0011, 0b1001, 0b0011, 0b0001]);
+/// ```
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ScheduledScanner {
+    periods: [u16; 4],
+    counters: [u16; 4],
+}
+
+impl ScheduledScanner {
+    /// Creates a scheduler with the given periods in ticks, indexed by
+    /// channel number.
+    ///
+    /// A period of 0 never schedules the channel. All scheduled channels
+    /// are due on the first tick.
+    pub const fn new(periods: [u16; 4]) -> Self {
+        Self {
+            periods,
+            counters: [0; 4],
+        }
+    }
+
+    /// Advances the scheduler by one tick, returning the channels that are
+    /// due on this tick.
+    pub fn tick(&mut self) -> ChannelSet {
+        let mut bits = 0;
+        for ch in 0..4 {
+            if self.periods[ch] == 0 {
+                continue;
+            }
+            if self.counters[ch] == 0 {
+                bits |= 1 << ch;
+                self.counters[ch] = self.periods[ch];
+            }
+            self.counters[ch] -= 1;
+        }
+        ChannelSet(bits)
+    }
+}
+
+/// Snapshot of the values of all configuration registers.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GoldenSnapshot([u8; CONFIG_REGISTERS.len()]);
+
+impl GoldenSnapshot {
+    /// Returns the register values in ascending address order.
+    pub const fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
 /// Channel operational mode.
 #[derive(Clone, Copy)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -111,6 +1208,24 @@ pub enum ScanRate {
     Lowest = 0x03,
 }
 
+impl ScanRate {
+    /// Nominal samples per second, or `u16::MAX` for continuous scanning.
+    ///
+    /// ```
+    /// assert_eq!(ldc3114::ScanRate::Medium.sps(), 40);
+    /// ```
+    pub const fn sps(self) -> u16 {
+        match self {
+            Self::Continuous => u16::MAX,
+            Self::Highest => 160,
+            Self::High => 80,
+            Self::Medium => 40,
+            Self::Low => 20,
+            Self::Lowest => 10,
+        }
+    }
+}
+
 /// Scan rate in low power mode.
 #[derive(Clone, Copy)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -126,6 +1241,23 @@ pub enum LowPowerScanRate {
     Low = 0x03,
 }
 
+impl LowPowerScanRate {
+    /// Nominal samples per second in thousandths, since the low power scan
+    /// rates are fractional.
+    ///
+    /// ```
+    /// assert_eq!(ldc3114::LowPowerScanRate::Low.sps_milli(), 625);
+    /// ```
+    pub const fn sps_milli(self) -> u32 {
+        match self {
+            Self::Highest => 5_000,
+            Self::High => 2_500,
+            Self::Medium => 1_250,
+            Self::Low => 625,
+        }
+    }
+}
+
 /// Interrupt polarity.
 #[derive(Clone, Copy)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -137,6 +1269,32 @@ pub enum InterruptPolarity {
     ActiveHigh = 1,
 }
 
+/// Event driving the INTB pin.
+///
+/// The source is selected by INTPOL:BTN_ALG_EN. The device cannot assert
+/// INTB on both kinds of events at the same time, so
+/// [`InterruptSource::Both`] is rejected when written.
+///
+/// INTB is a level output, there is no pulse mode: once asserted, it stays
+/// asserted until the event is cleared by reading the OUT register. When
+/// using an edge-triggered GPIO interrupt, the OUT register must be read on
+/// every interrupt, otherwise no further edge is generated.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum InterruptSource {
+    /// INTB is asserted when new data is available (BTN_ALG_EN = 0).
+    /// The button algorithm is disabled in this case.
+    DataReady,
+    /// INTB is asserted when any OUTX button output changes (BTN_ALG_EN = 1).
+    ButtonChange,
+    /// INTB is asserted both when new data is available and when any OUTX
+    /// button output changes.
+    ///
+    /// No BTN_ALG_EN setting selects this, so setting it returns
+    /// [`Error::InvalidParameter`] and it is never read back.
+    Both,
+}
+
 /// Button output polarity for pin OUTX.
 #[derive(Clone, Copy)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -196,6 +1354,17 @@ pub enum FrequencyRange {
     Freq10MHzTo30MHz = 0x40,
 }
 
+impl FrequencyRange {
+    /// Returns the highest sensor frequency of the range in Hz.
+    const fn max_hz(self) -> u32 {
+        match self {
+            FrequencyRange::Freq1MHzTo3_3MHz => 3_300_000,
+            FrequencyRange::Freq3_3MHzTo10MHz => 10_000_000,
+            FrequencyRange::Freq10MHzTo30MHz => 30_000_000,
+        }
+    }
+}
+
 /// Sensor configuration struct.
 #[derive(Clone, Copy)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -231,6 +1400,75 @@ pub enum FastTrackingFactor {
     Three = 3,
 }
 
+impl FastTrackingFactor {
+    /// Decodes the FTF field of a channel from the value of its register.
+    pub(crate) const fn from_register(value: u8, field: BitField) -> Self {
+        match field.get(value) {
+            0 => Self::Zero,
+            1 => Self::One,
+            2 => Self::Two,
+            _ => Self::Three,
+        }
+    }
+}
+
+/// Error returned when a register value doesn't encode any variant of an enum.
+///
+/// Every enum encoded into a register decodes back to the same variant:
+///
+/// ```
+/// use ldc3114::*;
+///
+/// macro_rules! assert_round_trip {
+///     ($Enum:ident: $($Variant:ident),*) => {
+///         $(assert!(matches!(
+///             $Enum::try_from($Enum::$Variant as u8),
+///             Ok($Enum::$Variant)
+///         ));)*
+///     };
+/// }
+///
+/// assert_round_trip!(ScanRate: Continuous, Highest, High, Medium, Low, Lowest);
+/// assert_round_trip!(LowPowerScanRate: Highest, High, Medium, Low);
+/// assert_round_trip!(RpRange: Rp50OhmTo4kOhm, Rp800OhmTo10kOhm);
+/// assert_round_trip!(FrequencyRange: Freq1MHzTo3_3MHz, Freq3_3MHzTo10MHz, Freq10MHzTo30MHz);
+/// assert_round_trip!(CounterScale: Zero, One, Two, Three);
+/// assert_round_trip!(FastTrackingFactor: Zero, One, Two, Three);
+/// assert_round_trip!(InterruptPolarity: ActiveLow, ActiveHigh);
+/// assert_round_trip!(OutputPolarity: ActiveLow, ActiveHigh);
+/// assert_round_trip!(DataPolarity: Inverted, Normal);
+///
+/// assert!(matches!(ScanRate::try_from(0x05), Err(InvalidEncoding(0x05))));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InvalidEncoding(pub u8);
+
+macro_rules! impl_try_from_u8 {
+    ($Enum:ident: $($Variant:ident),*) => {
+        impl TryFrom<u8> for $Enum {
+            type Error = InvalidEncoding;
+
+            fn try_from(value: u8) -> Result<Self, Self::Error> {
+                $(if value == $Enum::$Variant as u8 {
+                    return Ok($Enum::$Variant);
+                })*
+                Err(InvalidEncoding(value))
+            }
+        }
+    };
+}
+
+impl_try_from_u8!(ScanRate: Continuous, Highest, High, Medium, Low, Lowest);
+impl_try_from_u8!(LowPowerScanRate: Highest, High, Medium, Low);
+impl_try_from_u8!(RpRange: Rp50OhmTo4kOhm, Rp800OhmTo10kOhm);
+impl_try_from_u8!(FrequencyRange: Freq1MHzTo3_3MHz, Freq3_3MHzTo10MHz, Freq10MHzTo30MHz);
+impl_try_from_u8!(CounterScale: Zero, One, Two, Three);
+impl_try_from_u8!(FastTrackingFactor: Zero, One, Two, Three);
+impl_try_from_u8!(InterruptPolarity: ActiveLow, ActiveHigh);
+impl_try_from_u8!(OutputPolarity: ActiveLow, ActiveHigh);
+impl_try_from_u8!(DataPolarity: Inverted, Normal);
+
 /// Channel configuration struct.
 #[derive(Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -338,4 +1576,736 @@ impl DeviceConfig {
             antitwist: 0x00,
         }
     }
+
+    /// Configuration for four sensors forming a linear slider or a rotary.
+    ///
+    /// The electrodes are expected to be laid out in channel order along the
+    /// slider (or around the rotary), close enough that a target between two
+    /// electrodes is seen by both channels. The position is then interpolated
+    /// from the processed data of neighboring channels, so all four channels
+    /// are enabled and none of them is part of the max-win group, which would
+    /// only keep the strongest channel asserted. The button timeout is
+    /// disabled so that a finger resting on the slider keeps being tracked.
+    pub const fn slider_mode() -> Self {
+        const fn slider_channel<T: ChannelRegisters>(ch: T) -> ChannelConfig {
+            ChannelConfig {
+                mode: ChannelMode::NormalAndLowPowerMode,
+                enable_max_win_button_algorithm: false,
+                ..ChannelConfig::const_default(ch)
+            }
+        }
+
+        Self {
+            ch0: slider_channel(Channel0),
+            ch1: slider_channel(Channel1),
+            ch2: slider_channel(Channel2),
+            ch3: slider_channel(Channel3),
+            enable_button_timeout: false,
+            ..Self::const_default()
+        }
+    }
+
+    /// Configuration for maximum-sensitivity proximity detection on a single
+    /// channel, with all other channels disabled.
+    ///
+    /// The channel uses the maximum gain, and its baseline tracking is as slow
+    /// as possible and paused while its output is asserted, so that an
+    /// approaching object isn't tracked out as it slowly gets closer. The
+    /// button timeout is disabled so that an object may stay in range
+    /// indefinitely. The tradeoff is that slow baseline tracking makes the
+    /// channel sensitive to drift, e.g. from temperature changes, which may
+    /// cause false detections.
+    pub const fn proximity_mode<T: ChannelRegisters>(ch: T) -> Self {
+        const fn disabled<T: ChannelRegisters>(ch: T) -> ChannelConfig {
+            ChannelConfig {
+                mode: ChannelMode::Disabled,
+                ..ChannelConfig::const_default(ch)
+            }
+        }
+
+        let proximity = ChannelConfig {
+            mode: ChannelMode::NormalAndLowPowerMode,
+            gain: 0x3F,
+            fast_tracking_factor: FastTrackingFactor::Zero,
+            baseline_tracking_pause: true,
+            ..ChannelConfig::const_default(ch)
+        };
+
+        let mut config = Self {
+            ch0: disabled(Channel0),
+            ch1: disabled(Channel1),
+            ch2: disabled(Channel2),
+            ch3: disabled(Channel3),
+            enable_button_timeout: false,
+            baseline_tracking_increment_np: 0x00,
+            baseline_tracking_increment_lp: 0x00,
+            ..Self::const_default()
+        };
+        match T::CH {
+            0 => config.ch0 = proximity,
+            1 => config.ch1 = proximity,
+            2 => config.ch2 = proximity,
+            _ => config.ch3 = proximity,
+        }
+        config
+    }
+
+    /// Checks that all fields fit in their register encoding and that the LC
+    /// divider is compatible with the enabled channels, see
+    /// [`Self::validate_lc_divider`].
+    ///
+    /// The first offending field is reported.
+    ///
+    /// ```
+    /// use ldc3114::{ConfigError, DeviceConfig};
+    ///
+    /// let mut config = DeviceConfig::const_default();
+    /// assert!(config.validate().is_ok());
+    ///
+    /// config.ch2.gain = 0x40;
+    /// assert!(matches!(
+    ///     config.validate(),
+    ///     Err(ConfigError::GainOutOfRange { channel: 2 })
+    /// ));
+    /// ```
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let channels = [&self.ch0, &self.ch1, &self.ch2, &self.ch3];
+        for (channel, ch) in (0..).zip(channels) {
+            if !GAIN.fits(ch.gain) {
+                return Err(ConfigError::GainOutOfRange { channel });
+            }
+            if !SENCY.fits(ch.sensor_config.cycle_count) {
+                return Err(ConfigError::CycleCountOutOfRange { channel });
+            }
+        }
+
+        if !BASE_INC.fits(self.baseline_tracking_increment_np) {
+            return Err(ConfigError::BaselineTrackingIncrementNpOutOfRange);
+        }
+        if !BASE_INC.fits(self.baseline_tracking_increment_lp) {
+            return Err(ConfigError::BaselineTrackingIncrementLpOutOfRange);
+        }
+        if !LC_DIVIDER.fits(self.lc_divider) {
+            return Err(ConfigError::LcDividerOutOfRange);
+        }
+        if !HYST.fits(self.hysteresis) {
+            return Err(ConfigError::HysteresisOutOfRange);
+        }
+        if !TWIST.fits(self.antitwist) {
+            return Err(ConfigError::AntitwistOutOfRange);
+        }
+
+        self.validate_lc_divider()
+    }
+
+    /// Checks that the LC divider is compatible with the frequency range of
+    /// every enabled channel.
+    ///
+    /// The highest frequency of each range divided by `2^lc_divider` must
+    /// not exceed [`MAX_DIVIDED_SENSOR_FREQUENCY_HZ`].
+    ///
+    /// ```
+    /// use ldc3114::{ConfigError, DeviceConfig, FrequencyRange};
+    ///
+    /// let mut config = DeviceConfig::const_default();
+    /// config.ch0.sensor_config.frequency_range = FrequencyRange::Freq10MHzTo30MHz;
+    /// assert!(config.validate_lc_divider().is_ok());
+    ///
+    /// config.lc_divider = 2;
+    /// assert!(matches!(
+    ///     config.validate_lc_divider(),
+    ///     Err(ConfigError::LcDividerIncompatible { channel: 0 })
+    /// ));
+    /// ```
+    pub fn validate_lc_divider(&self) -> Result<(), ConfigError> {
+        let channels = [&self.ch0, &self.ch1, &self.ch2, &self.ch3];
+        for (channel, ch) in (0..).zip(channels) {
+            let max_hz = ch.sensor_config.frequency_range.max_hz();
+            if !matches!(ch.mode, ChannelMode::Disabled)
+                && max_hz >> self.lc_divider.min(31) > MAX_DIVIDED_SENSOR_FREQUENCY_HZ
+            {
+                return Err(ConfigError::LcDividerIncompatible { channel });
+            }
+        }
+        Ok(())
+    }
+
+    /// Encodes the configuration into register values.
+    ///
+    /// Each entry holds a register, its value and the mask of the bits
+    /// of the register that are owned by the configuration. Reserved bits are
+    /// never part of the mask, so they are preserved when writing and ignored
+    /// when verifying.
+    fn encode(&self) -> [(Register, u8, u8); 24] {
+        fn en_bits<T: ChannelRegisters>(_ch: T, mode: ChannelMode) -> u8 {
+            match mode {
+                ChannelMode::Disabled => 0x00,
+                ChannelMode::NormalMode => T::EN_BIT,
+                ChannelMode::NormalAndLowPowerMode => T::EN_BIT | T::LPEN_BIT,
+            }
+        }
+
+        fn btpause_maxwin_bits<T: ChannelRegisters>(_ch: T, config: &ChannelConfig) -> u8 {
+            let mut bits = 0x00;
+            if config.baseline_tracking_pause {
+                bits |= T::BTPAUSE_BIT;
+            }
+            if config.enable_max_win_button_algorithm {
+                bits |= T::MAXWIN_BIT;
+            }
+            bits
+        }
+
+        fn common_deform_bits<T: ChannelRegisters>(_ch: T, config: &ChannelConfig) -> u8 {
+            let mut bits = 0x00;
+            if config.enable_anticommon_algorithm {
+                bits |= T::ANTICOM_BIT;
+            }
+            if config.enable_antideform_algorithm {
+                bits |= T::ANTIDFORM_BIT;
+            }
+            bits
+        }
+
+        fn opol_dpol_bits<T: ChannelRegisters>(_ch: T, config: &ChannelConfig) -> u8 {
+            let mut bits = 0x00;
+            if let OutputPolarity::ActiveHigh = config.output_polarity {
+                bits |= T::OPOL_BIT;
+            }
+            if let DataPolarity::Normal = config.data_polarity {
+                bits |= T::DPOL_BIT;
+            }
+            bits
+        }
+
+        fn cntsc_bits<T: ChannelRegisters>(_ch: T, config: &ChannelConfig) -> u8 {
+            (config.counter_scale as u8) << cntsc_field::<T>().offset
+        }
+
+        fn ftf_bits<T: ChannelRegisters>(_ch: T, config: &ChannelConfig) -> u8 {
+            (config.fast_tracking_factor as u8) << ftf_field::<T>().offset
+        }
+
+        fn sensor_config_bits(config: &SensorConfig) -> u8 {
+            config.cycle_count | config.rp_range as u8 | config.frequency_range as u8
+        }
+
+        let en = en_bits(Channel0, self.ch0.mode)
+            | en_bits(Channel1, self.ch1.mode)
+            | en_bits(Channel2, self.ch2.mode)
+            | en_bits(Channel3, self.ch3.mode);
+
+        let mut intpol = (self.enable_reset_of_button_baseline_tracking as u8) << 4;
+        intpol |= (self.enable_button_press_detection_algorithm as u8) << 3;
+        intpol |= (self.interrupt_polarity as u8) << 2;
+        intpol |= ((!self.enable_button_timeout) as u8) << 1;
+        intpol |= (!self.enable_max_out_check) as u8;
+
+        let btpause_maxwin = btpause_maxwin_bits(Channel0, &self.ch0)
+            | btpause_maxwin_bits(Channel1, &self.ch1)
+            | btpause_maxwin_bits(Channel2, &self.ch2)
+            | btpause_maxwin_bits(Channel3, &self.ch3);
+
+        let common_deform = common_deform_bits(Channel0, &self.ch0)
+            | common_deform_bits(Channel1, &self.ch1)
+            | common_deform_bits(Channel2, &self.ch2)
+            | common_deform_bits(Channel3, &self.ch3);
+
+        let opol_dpol = opol_dpol_bits(Channel0, &self.ch0)
+            | opol_dpol_bits(Channel1, &self.ch1)
+            | opol_dpol_bits(Channel2, &self.ch2)
+            | opol_dpol_bits(Channel3, &self.ch3);
+
+        let cntsc = cntsc_bits(Channel0, &self.ch0)
+            | cntsc_bits(Channel1, &self.ch1)
+            | cntsc_bits(Channel2, &self.ch2)
+            | cntsc_bits(Channel3, &self.ch3);
+
+        let scfg0 = sensor_config_bits(&self.ch0.sensor_config);
+        let scfg1 = sensor_config_bits(&self.ch1.sensor_config);
+        let scfg2 = sensor_config_bits(&self.ch2.sensor_config);
+        let scfg3 = sensor_config_bits(&self.ch3.sensor_config);
+
+        let ftf1_2 = ftf_bits(Channel1, &self.ch1) | ftf_bits(Channel2, &self.ch2);
+
+        [
+            (Register::En, en, 0xFF),
+            (Register::Gain0, self.ch0.gain, GAIN.mask),
+            (Register::Gain1, self.ch1.gain, GAIN.mask),
+            (Register::Gain2, self.ch2.gain, GAIN.mask),
+            (Register::Gain3, self.ch3.gain, GAIN.mask),
+            (
+                Register::NpScanRate,
+                self.scan_rate as u8,
+                NP_SCAN_RATE.mask,
+            ),
+            (
+                Register::LpScanRate,
+                self.low_power_scan_rate as u8,
+                LP_SCAN_RATE.mask,
+            ),
+            (Register::IntPol, intpol, INTPOL_FIELDS),
+            (
+                Register::NpBaseInc,
+                self.baseline_tracking_increment_np,
+                BASE_INC.mask,
+            ),
+            (
+                Register::LpBaseInc,
+                self.baseline_tracking_increment_lp,
+                BASE_INC.mask,
+            ),
+            (Register::BtPauseMaxWin, btpause_maxwin, 0xFF),
+            (Register::LcDivider, self.lc_divider, LC_DIVIDER.mask),
+            (Register::Hyst, self.hysteresis, HYST.mask),
+            (Register::Twist, self.antitwist, TWIST.mask),
+            (Register::CommonDeform, common_deform, 0xFF),
+            (Register::OpolDpol, opol_dpol, 0xFF),
+            (Register::Cntsc, cntsc, 0xFF),
+            (Register::Sensor0Config, scfg0, 0xFF),
+            (Register::Sensor1Config, scfg1, 0xFF),
+            (Register::Sensor2Config, scfg2, 0xFF),
+            (Register::Sensor3Config, scfg3, 0xFF),
+            (Register::Ftf0, ftf_bits(Channel0, &self.ch0), FTF0_MASK),
+            (Register::Ftf1_2, ftf1_2, FTF1_MASK | FTF2_MASK),
+            (Register::Ftf3, ftf_bits(Channel3, &self.ch3), FTF3_MASK),
+        ]
+    }
+
+    /// Decodes the configuration from register values, reversing
+    /// [`Self::encode`].
+    ///
+    /// Only the defined fields are decoded, reserved bits are ignored.
+    fn decode(value: impl Fn(Register) -> u8) -> Result<Self, InvalidEncoding> {
+        fn decode_channel<T: ChannelRegisters>(
+            ch: T,
+            value: &impl Fn(Register) -> u8,
+        ) -> Result<ChannelConfig, InvalidEncoding> {
+            let en = value(Register::En);
+            let btpause_maxwin = value(Register::BtPauseMaxWin);
+            let common_deform = value(Register::CommonDeform);
+            let opol_dpol = value(Register::OpolDpol);
+            let cntsc = cntsc_field::<T>().get(value(Register::Cntsc));
+            let ftf = ftf_field::<T>().get(value(ch.ftf()));
+            let scfg = value(ch.sensor_config());
+
+            let mode = if en & T::EN_BIT == 0 {
+                ChannelMode::Disabled
+            } else if en & T::LPEN_BIT == 0 {
+                ChannelMode::NormalMode
+            } else {
+                ChannelMode::NormalAndLowPowerMode
+            };
+
+            Ok(ChannelConfig {
+                mode,
+                gain: GAIN.get(value(ch.gain())),
+                output_polarity: if opol_dpol & T::OPOL_BIT != 0 {
+                    OutputPolarity::ActiveHigh
+                } else {
+                    OutputPolarity::ActiveLow
+                },
+                data_polarity: if opol_dpol & T::DPOL_BIT != 0 {
+                    DataPolarity::Normal
+                } else {
+                    DataPolarity::Inverted
+                },
+                counter_scale: CounterScale::try_from(cntsc)?,
+                sensor_config: SensorConfig {
+                    rp_range: RpRange::try_from(scfg & RP_RANGE.mask)?,
+                    frequency_range: FrequencyRange::try_from(scfg & FREQ.mask)?,
+                    cycle_count: SENCY.get(scfg),
+                },
+                fast_tracking_factor: FastTrackingFactor::try_from(ftf)?,
+                enable_anticommon_algorithm: common_deform & T::ANTICOM_BIT != 0,
+                enable_antideform_algorithm: common_deform & T::ANTIDFORM_BIT != 0,
+                enable_max_win_button_algorithm: btpause_maxwin & T::MAXWIN_BIT != 0,
+                baseline_tracking_pause: btpause_maxwin & T::BTPAUSE_BIT != 0,
+            })
+        }
+
+        let intpol = value(Register::IntPol);
+        Ok(Self {
+            ch0: decode_channel(Channel0, &value)?,
+            ch1: decode_channel(Channel1, &value)?,
+            ch2: decode_channel(Channel2, &value)?,
+            ch3: decode_channel(Channel3, &value)?,
+            scan_rate: ScanRate::try_from(NP_SCAN_RATE.get(value(Register::NpScanRate)))?,
+            low_power_scan_rate: LowPowerScanRate::try_from(
+                LP_SCAN_RATE.get(value(Register::LpScanRate)),
+            )?,
+            enable_max_out_check: intpol & DIS_BTB_MO == 0,
+            enable_button_timeout: intpol & DIS_BTN_TO == 0,
+            interrupt_polarity: if intpol & INTPOL != 0 {
+                InterruptPolarity::ActiveHigh
+            } else {
+                InterruptPolarity::ActiveLow
+            },
+            enable_button_press_detection_algorithm: intpol & BTN_ALG_EN != 0,
+            enable_reset_of_button_baseline_tracking: intpol & BTSRT_EN != 0,
+            baseline_tracking_increment_np: BASE_INC.get(value(Register::NpBaseInc)),
+            baseline_tracking_increment_lp: BASE_INC.get(value(Register::LpBaseInc)),
+            lc_divider: LC_DIVIDER.get(value(Register::LcDivider)),
+            hysteresis: HYST.get(value(Register::Hyst)),
+            antitwist: TWIST.get(value(Register::Twist)),
+        })
+    }
+}
+
+/// Builder for [`DeviceConfig`], starting from [`DeviceConfig::const_default`].
+///
+/// The builder only assembles the configuration, it doesn't touch the bus.
+/// Values are checked when the configuration is written to the device.
+#[derive(Clone)]
+#[must_use]
+pub struct DeviceConfigBuilder {
+    config: DeviceConfig,
+}
+
+impl DeviceConfigBuilder {
+    /// Creates a builder with the default configuration.
+    pub const fn new() -> Self {
+        Self {
+            config: DeviceConfig::const_default(),
+        }
+    }
+
+    /// Sets the given channels to the given mode and disables all others.
+    pub const fn with_channels_enabled(mut self, set: ChannelSet, mode: ChannelMode) -> Self {
+        const fn mode_for(set: ChannelSet, ch: u8, mode: ChannelMode) -> ChannelMode {
+            if set.bits() & (1 << ch) != 0 {
+                mode
+            } else {
+                ChannelMode::Disabled
+            }
+        }
+
+        self.config.ch0.mode = mode_for(set, 0, mode);
+        self.config.ch1.mode = mode_for(set, 1, mode);
+        self.config.ch2.mode = mode_for(set, 2, mode);
+        self.config.ch3.mode = mode_for(set, 3, mode);
+        self
+    }
+
+    /// Sets the configuration of the given channel.
+    pub const fn with_channel<T: ChannelRegisters>(
+        mut self,
+        _ch: T,
+        config: ChannelConfig,
+    ) -> Self {
+        match T::CH {
+            0 => self.config.ch0 = config,
+            1 => self.config.ch1 = config,
+            2 => self.config.ch2 = config,
+            _ => self.config.ch3 = config,
+        }
+        self
+    }
+
+    /// Sets the scan rate in normal power mode.
+    pub const fn with_scan_rate(mut self, scan_rate: ScanRate) -> Self {
+        self.config.scan_rate = scan_rate;
+        self
+    }
+
+    /// Sets the scan rate in low power mode.
+    pub const fn with_low_power_scan_rate(mut self, scan_rate: LowPowerScanRate) -> Self {
+        self.config.low_power_scan_rate = scan_rate;
+        self
+    }
+
+    /// Sets the interrupt polarity.
+    pub const fn with_interrupt_polarity(mut self, polarity: InterruptPolarity) -> Self {
+        self.config.interrupt_polarity = polarity;
+        self
+    }
+
+    /// Sets the baseline tracking increments in normal and low power mode.
+    pub const fn with_baseline_tracking_increments(mut self, np: u8, lp: u8) -> Self {
+        self.config.baseline_tracking_increment_np = np;
+        self.config.baseline_tracking_increment_lp = lp;
+        self
+    }
+
+    /// Sets the LC oscillation frequency divider.
+    pub const fn with_lc_divider(mut self, lc_divider: u8) -> Self {
+        self.config.lc_divider = lc_divider;
+        self
+    }
+
+    /// Sets the hysteresis for the threshold of the button algorithm.
+    pub const fn with_hysteresis(mut self, hysteresis: u8) -> Self {
+        self.config.hysteresis = hysteresis;
+        self
+    }
+
+    /// Sets the anti-twist threshold of the button algorithm.
+    pub const fn with_antitwist(mut self, antitwist: u8) -> Self {
+        self.config.antitwist = antitwist;
+        self
+    }
+
+    /// Returns the assembled configuration.
+    pub const fn build(self) -> DeviceConfig {
+        self.config
+    }
+}
+
+impl Default for DeviceConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_logic_states_edges() {
+        let prev = OutputLogicStates::from_bits(OUT1 | OUT2);
+        let now = OutputLogicStates::from_bits(DATA_RDY | OUT0 | OUT2);
+
+        assert_eq!(now.rising(&prev), [true, false, false, false]);
+        assert_eq!(now.falling(&prev), [false, true, false, false]);
+    }
+
+    #[test]
+    fn output_logic_states_without_change_have_no_edges() {
+        let prev = OutputLogicStates::from_bits(OUT3);
+        // DATA_RDY isn't a button output.
+        let now = OutputLogicStates::from_bits(DATA_RDY | OUT3);
+
+        assert_eq!(now.rising(&prev), [false; 4]);
+        assert_eq!(now.falling(&prev), [false; 4]);
+    }
+
+    #[test]
+    fn channel_set_insert_and_iterate() {
+        let mut set = ChannelSet::new();
+        assert!(set.iter().eq([]));
+
+        set.insert(Channel2);
+        set.insert(Channel0);
+        // Inserting a channel twice has no effect.
+        set.insert(Channel2);
+
+        assert!(set.iter().eq([0, 2]));
+        assert_eq!(set.bits(), 0b0101);
+    }
+
+    #[test]
+    fn channel_set_operations() {
+        assert!(ChannelSet::NONE.iter().eq([]));
+        assert!(ChannelSet::ALL.iter().eq([0, 1, 2, 3]));
+        assert!(ChannelSet::default() == ChannelSet::NONE);
+
+        let mut set = ChannelSet::NONE.with(Channel3).with(Channel1);
+        assert!(set.contains(Channel1) && set.contains(Channel3));
+        assert!(!set.contains(Channel0) && !set.contains(Channel2));
+
+        set.remove(Channel3);
+        // Removing a channel that isn't in the set has no effect.
+        set.remove(Channel0);
+        assert!(set == ChannelSet::NONE.with(Channel1));
+
+        let set = ChannelSet::from([true, false, false, true]);
+        // Iteration is in ascending channel order, regardless of insertion.
+        assert!(set.iter().eq([0, 3]));
+        assert!(ChannelSet::from([true; 4]) == ChannelSet::ALL);
+    }
+
+    #[test]
+    fn slider_mode_enables_all_channels_without_max_win() {
+        let config = DeviceConfig::slider_mode();
+
+        for ch in [&config.ch0, &config.ch1, &config.ch2, &config.ch3] {
+            assert!(matches!(ch.mode, ChannelMode::NormalAndLowPowerMode));
+            assert!(!ch.enable_max_win_button_algorithm);
+        }
+        assert!(!config.enable_button_timeout);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_lc_divider_rejects_divided_frequencies_above_the_maximum() {
+        let mut config = DeviceConfig::const_default();
+        config.ch3.sensor_config.frequency_range = FrequencyRange::Freq3_3MHzTo10MHz;
+
+        // 10 MHz / 2^1 = 5 MHz is above the maximum.
+        config.lc_divider = 1;
+        assert!(matches!(
+            config.validate_lc_divider(),
+            Err(ConfigError::LcDividerIncompatible { channel: 3 })
+        ));
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::LcDividerIncompatible { channel: 3 })
+        ));
+
+        // 10 MHz / 2^2 = 2.5 MHz is fine.
+        config.lc_divider = 2;
+        assert!(config.validate_lc_divider().is_ok());
+
+        // 3.3 MHz is below the maximum even without division.
+        config.lc_divider = 0;
+        config.ch3.sensor_config.frequency_range = FrequencyRange::Freq1MHzTo3_3MHz;
+        assert!(config.validate_lc_divider().is_ok());
+
+        // Disabled channels aren't checked.
+        config.ch3.sensor_config.frequency_range = FrequencyRange::Freq10MHzTo30MHz;
+        config.ch3.mode = ChannelMode::Disabled;
+        assert!(config.validate_lc_divider().is_ok());
+    }
+
+    #[test]
+    fn status_accumulator_keeps_clear_on_read_flags_until_taken() {
+        let mut acc = StatusAccumulator::new();
+        acc.accumulate(CHIP_READY | OUT_STATUS | LC_WD);
+        // The next read no longer reports the flags cleared by the first one.
+        acc.accumulate(RDY_TO_WRITE);
+
+        let status = acc.status();
+        assert!(status.output_status && status.lc_sensor_watchdog_error);
+        // CHIP_READY and RDY_TO_WRITE reflect the latest read.
+        assert!(!status.chip_ready && status.ready_to_write);
+
+        let taken = acc.take();
+        assert!(taken.output_status && taken.lc_sensor_watchdog_error);
+        let status = acc.status();
+        assert!(!status.output_status && !status.lc_sensor_watchdog_error);
+        assert!(status.ready_to_write);
+    }
+
+    #[test]
+    fn raw_data_from_bytes_round_trips_without_overflow() {
+        for bytes in [[0xFF; 3], [0x00; 3], [0x80, 0x00, 0x01], [0x12, 0x34, 0x56]] {
+            let raw = raw_data_from_bytes(bytes);
+
+            let [top, msb, mid, lsb] = raw.to_be_bytes();
+            assert_eq!(top, 0);
+            assert_eq!([msb, mid, lsb], bytes);
+        }
+        assert_eq!(raw_data_from_bytes([0xFF; 3]), 0x00FF_FFFF);
+    }
+
+    #[test]
+    fn proximity_mode_uses_high_gain_and_slow_paused_tracking() {
+        let config = DeviceConfig::proximity_mode(Channel2);
+
+        let ch = &config.ch2;
+        assert!(matches!(ch.mode, ChannelMode::NormalAndLowPowerMode));
+        assert_eq!(ch.gain, 0x3F);
+        assert!(matches!(ch.fast_tracking_factor, FastTrackingFactor::Zero));
+        assert!(ch.baseline_tracking_pause);
+        assert_eq!(config.baseline_tracking_increment_np, 0);
+        assert_eq!(config.baseline_tracking_increment_lp, 0);
+        assert!(!config.enable_button_timeout);
+
+        for ch in [&config.ch0, &config.ch1, &config.ch3] {
+            assert!(matches!(ch.mode, ChannelMode::Disabled));
+        }
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn builder_enables_a_channel_group_and_disables_the_others() {
+        let channels = ChannelSet::NONE.with(Channel0).with(Channel2);
+        let config = DeviceConfigBuilder::new()
+            .with_scan_rate(ScanRate::Low)
+            .with_channels_enabled(channels, ChannelMode::NormalAndLowPowerMode)
+            .with_hysteresis(0x04)
+            .build();
+
+        assert!(matches!(
+            config.ch0.mode,
+            ChannelMode::NormalAndLowPowerMode
+        ));
+        assert!(matches!(config.ch1.mode, ChannelMode::Disabled));
+        assert!(matches!(
+            config.ch2.mode,
+            ChannelMode::NormalAndLowPowerMode
+        ));
+        assert!(matches!(config.ch3.mode, ChannelMode::Disabled));
+        // The other builder methods still apply.
+        assert!(matches!(config.scan_rate, ScanRate::Low));
+        assert_eq!(config.hysteresis, 0x04);
+    }
+
+    #[test]
+    fn device_config_decode_reverses_encode() {
+        fn encoded(config: &DeviceConfig) -> [(u8, u8); 24] {
+            config
+                .encode()
+                .map(|(register, value, _)| (register.addr(), value))
+        }
+
+        // Reserved bits are set, as they may be on the device.
+        fn round_trip(config: &DeviceConfig) -> DeviceConfig {
+            let registers = config.encode();
+            DeviceConfig::decode(|register| {
+                let (_, value, mask) = registers
+                    .iter()
+                    .find(|(r, _, _)| r.addr() == register.addr())
+                    .unwrap();
+                value | !mask
+            })
+            .unwrap()
+        }
+
+        let mut config = DeviceConfig::const_default();
+        config.ch0.mode = ChannelMode::NormalMode;
+        config.ch0.enable_max_win_button_algorithm = true;
+        config.ch1.mode = ChannelMode::Disabled;
+        config.ch1.fast_tracking_factor = FastTrackingFactor::Three;
+        config.ch1.enable_antideform_algorithm = true;
+        config.ch2.gain = 0x2A;
+        config.ch2.output_polarity = OutputPolarity::ActiveHigh;
+        config.ch2.data_polarity = DataPolarity::Inverted;
+        config.ch2.counter_scale = CounterScale::Two;
+        config.ch2.fast_tracking_factor = FastTrackingFactor::One;
+        config.ch2.baseline_tracking_pause = true;
+        config.ch3.enable_anticommon_algorithm = true;
+        config.ch3.sensor_config = SensorConfig {
+            rp_range: RpRange::Rp800OhmTo10kOhm,
+            frequency_range: FrequencyRange::Freq3_3MHzTo10MHz,
+            cycle_count: 0x1F,
+        };
+        config.scan_rate = ScanRate::Lowest;
+        config.low_power_scan_rate = LowPowerScanRate::High;
+        config.interrupt_polarity = InterruptPolarity::ActiveHigh;
+        config.enable_max_out_check = false;
+        config.enable_reset_of_button_baseline_tracking = false;
+        config.baseline_tracking_increment_np = 0x07;
+        config.baseline_tracking_increment_lp = 0x01;
+        config.lc_divider = 0x05;
+        config.hysteresis = 0x0F;
+        config.antitwist = 0x06;
+
+        let decoded = round_trip(&config);
+
+        assert_eq!(encoded(&decoded), encoded(&config));
+        assert!(matches!(decoded.ch0.mode, ChannelMode::NormalMode));
+        assert!(matches!(decoded.ch1.mode, ChannelMode::Disabled));
+        assert!(matches!(
+            decoded.ch1.fast_tracking_factor,
+            FastTrackingFactor::Three
+        ));
+        assert_eq!(decoded.ch2.gain, 0x2A);
+        assert!(matches!(
+            decoded.ch2.output_polarity,
+            OutputPolarity::ActiveHigh
+        ));
+        assert_eq!(decoded.ch3.sensor_config.cycle_count, 0x1F);
+        assert!(!decoded.enable_max_out_check);
+        assert_eq!(decoded.lc_divider, 0x05);
+        assert_eq!(decoded.antitwist, 0x06);
+
+        for config in [
+            DeviceConfig::slider_mode(),
+            DeviceConfig::proximity_mode(Channel1),
+        ] {
+            assert_eq!(encoded(&round_trip(&config)), encoded(&config));
+        }
+    }
 }
@@ -0,0 +1,238 @@
+//! Simulated LDC3114 on an I2C bus, for unit tests of the driver.
+
+extern crate std;
+
+use core::cell::RefCell;
+use core::convert::Infallible;
+use std::boxed::Box;
+use std::rc::Rc;
+use std::vec::Vec;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation};
+
+use crate::register::{CHIP_READY, CONFIG_MODE, RDY_TO_WRITE};
+use crate::{I2C_ADDR, Register};
+
+/// Hook called with the first register read and the register file.
+pub(crate) type ReadHook = Box<dyn FnMut(u8, &mut [u8; 256])>;
+
+/// Register file of an LDC3114.
+///
+/// Like the device, the register address auto-increments on every byte
+/// transferred. Every register write is logged, so tests can assert what
+/// reached the bus.
+pub(crate) struct MockI2c {
+    /// Register values, indexed by address.
+    pub(crate) regs: [u8; 256],
+    /// Register writes, in order, as `(address, value)`.
+    pub(crate) writes: Vec<(u8, u8)>,
+    /// Whether STATUS:RDY_TO_WRITE follows RESET:CONFIG_MODE. When unset,
+    /// the registers never become ready to be written.
+    pub(crate) ready_to_write: bool,
+    /// Number of upcoming transactions that aren't acknowledged.
+    pub(crate) nacks: u32,
+    /// Error returned by every transaction, e.g. to simulate a stuck bus.
+    pub(crate) bus_error: Option<ErrorKind>,
+    /// Called with the first register read after every transaction that
+    /// reads, e.g. to simulate a new conversion landing between two reads.
+    pub(crate) after_read: Option<ReadHook>,
+}
+
+impl MockI2c {
+    /// A device that is ready and reports the expected IDs.
+    pub(crate) fn new() -> Self {
+        let mut regs = [0; 256];
+        regs[Register::Status as usize] = CHIP_READY;
+        regs[Register::ManufacturerIdLsb as usize] = 0x49;
+        regs[Register::ManufacturerIdMsb as usize] = 0x54;
+        regs[Register::DeviceIdLsb as usize] = 0x00;
+        regs[Register::DeviceIdMsb as usize] = 0x10;
+        Self {
+            regs,
+            writes: Vec::new(),
+            ready_to_write: true,
+            nacks: 0,
+            bus_error: None,
+            after_read: None,
+        }
+    }
+
+    /// Value of a register.
+    pub(crate) fn get(&self, register: Register) -> u8 {
+        self.regs[register as usize]
+    }
+
+    /// Sets the value of a register without logging a write.
+    pub(crate) fn set(&mut self, register: Register, value: u8) {
+        self.regs[register as usize] = value;
+    }
+
+    fn write_byte(&mut self, addr: u8, value: u8) {
+        self.writes.push((addr, value));
+        self.regs[addr as usize] = value;
+        if addr == Register::Reset.addr() {
+            let status = &mut self.regs[Register::Status as usize];
+            if value & CONFIG_MODE != 0 && self.ready_to_write {
+                *status |= RDY_TO_WRITE;
+            } else {
+                *status &= !RDY_TO_WRITE;
+            }
+        }
+    }
+}
+
+impl ErrorType for MockI2c {
+    type Error = ErrorKind;
+}
+
+impl I2c for MockI2c {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if address != I2C_ADDR {
+            return Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+        }
+        if let Some(error) = self.bus_error {
+            return Err(error);
+        }
+        if self.nacks > 0 {
+            self.nacks -= 1;
+            return Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+        }
+
+        let mut pointer = 0u8;
+        let mut read = None;
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => {
+                    if let Some((&addr, values)) = bytes.split_first() {
+                        pointer = addr;
+                        for &value in values {
+                            self.write_byte(pointer, value);
+                            pointer = pointer.wrapping_add(1);
+                        }
+                    }
+                }
+                Operation::Read(buffer) => {
+                    read.get_or_insert(pointer);
+                    for byte in buffer.iter_mut() {
+                        *byte = self.regs[pointer as usize];
+                        pointer = pointer.wrapping_add(1);
+                    }
+                }
+            }
+        }
+
+        if let (Some(start), Some(after_read)) = (read, &mut self.after_read) {
+            after_read(start, &mut self.regs);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::i2c::I2c for MockI2c {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        I2c::transaction(self, address, operations)
+    }
+}
+
+/// Delay that returns immediately.
+pub(crate) struct NoDelay;
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::delay::DelayNs for NoDelay {
+    async fn delay_ns(&mut self, _ns: u32) {}
+}
+
+/// Runs a future to completion on the current thread.
+///
+/// The futures of the driver only wait on the mock, which is always ready,
+/// so the future is simply polled until it completes.
+#[cfg(feature = "async")]
+pub(crate) fn block_on<F: core::future::Future>(future: F) -> F::Output {
+    let mut future = core::pin::pin!(future);
+    let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+    loop {
+        if let core::task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+/// Pin or delay call recorded by [`MockPin`] and [`MockDelay`].
+#[derive(Debug, PartialEq)]
+pub(crate) enum Call {
+    Low,
+    High,
+    DelayMs(u32),
+    DelayUs(u32),
+    DelayNs(u32),
+}
+
+/// Log of calls shared by a [`MockPin`] and a [`MockDelay`], so that their
+/// order can be asserted.
+pub(crate) type CallLog = Rc<RefCell<Vec<Call>>>;
+
+/// Output pin that records every level it is driven to.
+pub(crate) struct MockPin(pub(crate) CallLog);
+
+impl embedded_hal::digital::ErrorType for MockPin {
+    type Error = Infallible;
+}
+
+impl OutputPin for MockPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.0.borrow_mut().push(Call::Low);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.0.borrow_mut().push(Call::High);
+        Ok(())
+    }
+}
+
+/// Delay that returns immediately and records how long it was asked to wait.
+pub(crate) struct MockDelay(pub(crate) CallLog);
+
+impl DelayNs for MockDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.0.borrow_mut().push(Call::DelayNs(ns));
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        self.0.borrow_mut().push(Call::DelayUs(us));
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.0.borrow_mut().push(Call::DelayMs(ms));
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::delay::DelayNs for MockDelay {
+    async fn delay_ns(&mut self, ns: u32) {
+        DelayNs::delay_ns(self, ns);
+    }
+
+    async fn delay_us(&mut self, us: u32) {
+        DelayNs::delay_us(self, us);
+    }
+
+    async fn delay_ms(&mut self, ms: u32) {
+        DelayNs::delay_ms(self, ms);
+    }
+}
@@ -97,8 +97,109 @@ impl Register {
                 | Register::ManufacturerIdMsb
         )
     }
+
+    /// Checks if the register can only be written in configuration mode.
+    pub fn requires_config_mode(self) -> bool {
+        matches!(
+            self,
+            Register::En
+                | Register::NpScanRate
+                | Register::Gain0
+                | Register::LpScanRate
+                | Register::Gain1
+                | Register::IntPol
+                | Register::Gain2
+                | Register::LpBaseInc
+                | Register::Gain3
+                | Register::NpBaseInc
+                | Register::BtPauseMaxWin
+                | Register::LcDivider
+                | Register::Hyst
+                | Register::Twist
+                | Register::CommonDeform
+                | Register::OpolDpol
+                | Register::Cntsc
+                | Register::Sensor0Config
+                | Register::Sensor1Config
+                | Register::Sensor2Config
+                | Register::Ftf0
+                | Register::Sensor3Config
+                | Register::Ftf1_2
+                | Register::Ftf3
+        )
+    }
 }
 
+impl Register {
+    /// Returns the position of the register in [`CONFIG_REGISTERS`], or
+    /// `None` if it doesn't hold device configuration.
+    pub(crate) const fn config_index(self) -> Option<usize> {
+        match self {
+            Register::En => Some(0),
+            Register::NpScanRate => Some(1),
+            Register::Gain0 => Some(2),
+            Register::LpScanRate => Some(3),
+            Register::Gain1 => Some(4),
+            Register::IntPol => Some(5),
+            Register::Gain2 => Some(6),
+            Register::LpBaseInc => Some(7),
+            Register::Gain3 => Some(8),
+            Register::NpBaseInc => Some(9),
+            Register::BtPauseMaxWin => Some(10),
+            Register::LcDivider => Some(11),
+            Register::Hyst => Some(12),
+            Register::Twist => Some(13),
+            Register::CommonDeform => Some(14),
+            Register::OpolDpol => Some(15),
+            Register::Cntsc => Some(16),
+            Register::Sensor0Config => Some(17),
+            Register::Sensor1Config => Some(18),
+            Register::Sensor2Config => Some(19),
+            Register::Ftf0 => Some(20),
+            Register::Sensor3Config => Some(21),
+            Register::Ftf1_2 => Some(22),
+            Register::Ftf3 => Some(23),
+            _ => None,
+        }
+    }
+}
+
+/// Registers holding the device configuration.
+pub(crate) const CONFIG_REGISTERS: [Register; 24] = [
+    Register::En,
+    Register::NpScanRate,
+    Register::Gain0,
+    Register::LpScanRate,
+    Register::Gain1,
+    Register::IntPol,
+    Register::Gain2,
+    Register::LpBaseInc,
+    Register::Gain3,
+    Register::NpBaseInc,
+    Register::BtPauseMaxWin,
+    Register::LcDivider,
+    Register::Hyst,
+    Register::Twist,
+    Register::CommonDeform,
+    Register::OpolDpol,
+    Register::Cntsc,
+    Register::Sensor0Config,
+    Register::Sensor1Config,
+    Register::Sensor2Config,
+    Register::Ftf0,
+    Register::Sensor3Config,
+    Register::Ftf1_2,
+    Register::Ftf3,
+];
+
+const _: () = {
+    let mut i = 0;
+    while i < CONFIG_REGISTERS.len() {
+        assert!(matches!(CONFIG_REGISTERS[i].config_index(), Some(index) if index == i));
+        i += 1;
+    }
+};
+
 // STATUS
 pub(crate) const OUT_STATUS: u8 = 0x80;
 pub(crate) const CHIP_READY: u8 = 0x40;
@@ -136,6 +237,7 @@ pub(crate) const BTN_ALG_EN: u8 = 0x08;
 pub(crate) const INTPOL: u8 = 0x04;
 pub(crate) const DIS_BTN_TO: u8 = 0x02;
 pub(crate) const DIS_BTB_MO: u8 = 0x01;
+pub(crate) const INTPOL_FIELDS: u8 = BTSRT_EN | BTN_ALG_EN | INTPOL | DIS_BTN_TO | DIS_BTB_MO;
 
 // BTPAUSE_MAXWIN
 pub(crate) const BTPAUSE3: u8 = 0x80;
@@ -177,6 +279,11 @@ pub(crate) const CNTSC2_OFFSET: u8 = 4;
 pub(crate) const CNTSC1_OFFSET: u8 = 2;
 pub(crate) const CNTSC0_OFFSET: u8 = 0;
 
+// SENSORn_CONFIG
+pub(crate) const RP_RANGE: BitField = BitField::new(0x80, 7);
+pub(crate) const FREQ: BitField = BitField::new(0x60, 5);
+pub(crate) const SENCY: BitField = BitField::new(0x1F, 0);
+
 // FTF0
 pub(crate) const FTF0_MASK: u8 = 0x06;
 pub(crate) const FTF0_OFFSET: u8 = 1;
@@ -191,6 +298,75 @@ pub(crate) const FTF1_OFFSET: u8 = 4;
 pub(crate) const FTF3_MASK: u8 = 0x03;
 pub(crate) const FTF3_OFFSET: u8 = 0;
 
+// GAINn
+pub(crate) const GAIN: BitField = BitField::new(0x3F, 0);
+
+// NP_SCAN_RATE
+pub(crate) const NP_SCAN_RATE: BitField = BitField::new(0x0F, 0);
+
+// LP_SCAN_RATE
+pub(crate) const LP_SCAN_RATE: BitField = BitField::new(0x03, 0);
+
+// NP_BASE_INC / LP_BASE_INC
+pub(crate) const BASE_INC: BitField = BitField::new(0x07, 0);
+
+// LC_DIVIDER
+pub(crate) const LC_DIVIDER: BitField = BitField::new(0x07, 0);
+
+// HYST
+pub(crate) const HYST: BitField = BitField::new(0x0F, 0);
+
+// TWIST
+pub(crate) const TWIST: BitField = BitField::new(0x07, 0);
+
+// Channel groups
+pub(crate) const MAXWIN_GROUP: BitField = BitField::new(0x0F, 0);
+pub(crate) const ANTICOM_GROUP: BitField = BitField::new(0xF0, 4);
+pub(crate) const ANTIDFORM_GROUP: BitField = BitField::new(0x0F, 0);
+
+/// Field of contiguous bits in a register.
+#[derive(Clone, Copy)]
+pub(crate) struct BitField {
+    pub(crate) mask: u8,
+    pub(crate) offset: u8,
+}
+
+impl BitField {
+    /// Creates a field from its mask and the offset of its lowest bit.
+    pub(crate) const fn new(mask: u8, offset: u8) -> Self {
+        Self { mask, offset }
+    }
+
+    /// Extracts the value of the field from a register value.
+    pub(crate) const fn get(self, byte: u8) -> u8 {
+        (byte & self.mask) >> self.offset
+    }
+
+    /// Checks if `value` fits in the field.
+    pub(crate) const fn fits(self, value: u8) -> bool {
+        value <= self.mask >> self.offset
+    }
+
+    /// Returns `byte` with the field set to `value` and all other bits
+    /// untouched, or `None` if `value` doesn't fit in the field.
+    pub(crate) const fn set(self, byte: u8, value: u8) -> Option<u8> {
+        if !self.fits(value) {
+            return None;
+        }
+        Some((byte & !self.mask) | (value << self.offset))
+    }
+}
+
+/// CNTSC field of the given channel in the CNTSC register.
+pub(crate) const fn cntsc_field<T: ChannelRegisters>() -> BitField {
+    BitField::new(T::CNTSC_MASK, T::CNTSC_OFFSET)
+}
+
+/// FTF field of the given channel in its FTF register.
+pub(crate) const fn ftf_field<T: ChannelRegisters>() -> BitField {
+    BitField::new(T::FTF_MASK, T::FTF_OFFSET)
+}
+
 /// Channel registers.
 pub trait ChannelRegisters: Copy {
     /// Channel number.
@@ -223,14 +399,41 @@ pub trait ChannelRegisters: Copy {
     const DEFAULT_MODE: super::ChannelMode;
     /// Get the DATA_LSB register for this channel.
     fn data_lsb(&self) -> Register;
-    /// Get the RAW_DATA_LSB register for this channel.
-    fn raw_data_lsb(&self) -> Register;
+    /// Get the first RAW_DATA register for this channel, RAW_DATAn_3, which
+    /// holds the most significant byte.
+    fn raw_data_start(&self) -> Register;
     /// Get the GAIN register for this channel.
     fn gain(&self) -> Register;
     /// Get the SENSOR_CONFIG register for this channel.
     fn sensor_config(&self) -> Register;
     /// Get the FTF register for this channel.
     fn ftf(&self) -> Register;
+    /// Get the given kind of register for this channel.
+    fn register(&self, which: ChannelRegister) -> Register {
+        match which {
+            ChannelRegister::Data => self.data_lsb(),
+            ChannelRegister::RawData => self.raw_data_start(),
+            ChannelRegister::Gain => self.gain(),
+            ChannelRegister::SensorConfig => self.sensor_config(),
+            ChannelRegister::Ftf => self.ftf(),
+        }
+    }
+}
+
+/// Kinds of channel-specific registers.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ChannelRegister {
+    /// DATA_LSB register.
+    Data,
+    /// First RAW_DATA register.
+    RawData,
+    /// GAIN register.
+    Gain,
+    /// SENSOR_CONFIG register.
+    SensorConfig,
+    /// FTF register.
+    Ftf,
 }
 
 /// Representation of registers for channel 0.
@@ -273,7 +476,7 @@ macro_rules! impl_channel_registers {
             fn data_lsb(&self) -> Register {
                 Register::$Data
             }
-            fn raw_data_lsb(&self) -> Register {
+            fn raw_data_start(&self) -> Register {
                 Register::$RawData
             }
             fn gain(&self) -> Register {
@@ -293,3 +496,83 @@ impl_channel_registers!(Channel0: 0, Data0Lsb, RawData0_3, Gain0, Sensor0Config,
 impl_channel_registers!(Channel1: 1, Data1Lsb, RawData1_3, Gain1, Sensor1Config, Ftf1_2, EN1, LPEN1, BTPAUSE1, MAXWIN1, OPOL1, DPOL1, ANTICOM1, ANTIDFORM1, CNTSC1_MASK, CNTSC1_OFFSET, FTF1_MASK, FTF1_OFFSET, NormalMode);
 impl_channel_registers!(Channel2: 2, Data2Lsb, RawData2_3, Gain2, Sensor2Config, Ftf1_2, EN2, LPEN2, BTPAUSE2, MAXWIN2, OPOL2, DPOL2, ANTICOM2, ANTIDFORM2, CNTSC2_MASK, CNTSC2_OFFSET, FTF2_MASK, FTF2_OFFSET, NormalMode);
 impl_channel_registers!(Channel3: 3, Data3Lsb, RawData3_3, Gain3, Sensor3Config, Ftf3, EN3, LPEN3, BTPAUSE3, MAXWIN3, OPOL3, DPOL3, ANTICOM3, ANTIDFORM3, CNTSC3_MASK, CNTSC3_OFFSET, FTF3_MASK, FTF3_OFFSET, NormalMode);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_classification() {
+        // (register, read-only, requires configuration mode)
+        let table = [
+            (Register::Status, true, false),
+            (Register::Out, true, false),
+            (Register::Data0Lsb, true, false),
+            (Register::Data3Msb, true, false),
+            (Register::Reset, false, false),
+            (Register::En, false, true),
+            (Register::NpScanRate, false, true),
+            (Register::Gain0, false, true),
+            (Register::IntPol, false, true),
+            (Register::BtPauseMaxWin, false, true),
+            (Register::Cntsc, false, true),
+            (Register::Ftf1_2, false, true),
+            (Register::RawData0_3, true, false),
+            (Register::RawData3_1, true, false),
+            (Register::ManufacturerIdLsb, true, false),
+            (Register::DeviceIdMsb, true, false),
+        ];
+
+        for (register, read_only, config) in table {
+            assert_eq!(
+                register.is_read_only(),
+                read_only,
+                "{:#04x}",
+                register.addr()
+            );
+            assert_eq!(
+                register.requires_config_mode(),
+                config,
+                "{:#04x}",
+                register.addr()
+            );
+        }
+    }
+
+    #[test]
+    fn config_registers_require_config_mode() {
+        for register in CONFIG_REGISTERS {
+            assert!(register.requires_config_mode(), "{:#04x}", register.addr());
+            assert!(!register.is_read_only(), "{:#04x}", register.addr());
+        }
+    }
+
+    #[test]
+    fn bit_field_get_extracts_the_field() {
+        let field = BitField::new(0x30, 4);
+        assert_eq!(field.get(0x00), 0);
+        assert_eq!(field.get(0xEF), 2);
+        assert_eq!(field.get(0xFF), 3);
+        assert_eq!(SENCY.get(0xE4), 0x04);
+        assert_eq!(RP_RANGE.get(0x80), 1);
+    }
+
+    #[test]
+    fn bit_field_fits_only_values_within_its_width() {
+        assert!(GAIN.fits(0x3F));
+        assert!(!GAIN.fits(0x40));
+        assert!(HYST.fits(0x0F));
+        assert!(!HYST.fits(0x10));
+        assert!(cntsc_field::<Channel3>().fits(3));
+        assert!(!cntsc_field::<Channel3>().fits(4));
+    }
+
+    #[test]
+    fn bit_field_set_preserves_other_bits() {
+        let field = ftf_field::<Channel2>();
+        assert_eq!(field.set(0x3F, 2), Some(0xBF));
+        assert_eq!(field.set(0xFF, 0), Some(0x3F));
+        assert_eq!(GAIN.set(0xC0, 0x15), Some(0xD5));
+        assert_eq!(LC_DIVIDER.set(0xFF, 0x08), None);
+    }
+}
@@ -6,22 +6,116 @@ where
 {
     /// Creates a new driver instance for the LDC3114.
     pub fn new(i2c: I2C) -> Self {
-        Self {
-            i2c,
-            sency0: 4,
-            sency1: 4,
-            sency2: 4,
-            sency3: 4,
-            lcdiv: 3,
+        Self::from_parts(i2c, NoReset)
+    }
+
+    /// Creates a new driver instance for an LDC3114 that is already running.
+    ///
+    /// Unlike [`Self::new`], the cached sensor configuration is read from the
+    /// device, so derived values such as the sensor frequency returned by
+    /// [`Self::read_sensor_frequency`] are correct from the start. If the
+    /// device was left in configuration mode, it is returned to normal mode
+    /// to match the returned driver.
+    pub fn new_attach(i2c: I2C) -> Result<Self, Error<E>> {
+        let mut ldc = Self::new(i2c);
+        if ldc.read_register(Register::Reset)? & CONFIG_MODE != 0 {
+            ldc.write_register(Register::Reset, 0)?;
+        }
+        ldc.refresh_cache()?;
+        Ok(ldc)
+    }
+
+    /// Creates a new driver instance and verifies that the device on the bus
+    /// is an LDC3114, see [`Self::verify_device`].
+    ///
+    /// Use this instead of [`Self::new`] to catch wiring mistakes or a wrong
+    /// part at startup rather than through bogus data later on.
+    pub fn new_checked(i2c: I2C) -> Result<Self, Error<E>> {
+        let mut ldc = Self::new(i2c);
+        ldc.verify_device()?;
+        Ok(ldc)
+    }
+}
+
+impl<I2C, E, P> Ldc3114<I2C, Normal, P>
+where
+    I2C: embedded_hal::i2c::I2c + embedded_hal::i2c::ErrorType<Error = E>,
+    P: embedded_hal::digital::OutputPin,
+{
+    /// Creates a new driver instance for an LDC3114 whose supply is switched
+    /// by `reset`.
+    ///
+    /// The LDC3114 has no reset pin, so `reset` is expected to enable its VDD
+    /// supply, e.g. through a load switch or regulator enable, with the device
+    /// powered while the pin is high. The pin isn't driven until
+    /// [`Self::hardware_reset`] is called, so the device is assumed to be
+    /// fresh out of reset like with [`Ldc3114::new`].
+    pub fn new_with_reset(i2c: I2C, reset: P) -> Self {
+        Self::from_parts(i2c, reset)
+    }
+
+    /// Power-cycles the device through its supply enable, then waits until
+    /// the chip is ready.
+    ///
+    /// The pin is driven low for `off_time_ms` and then high again, after
+    /// which the device is given `power_up_ms` before STATUS:CHIP_READY is
+    /// polled. Both timings depend on the board rather than on the LDC3114:
+    /// `off_time_ms` must let VDD discharge below the power-on reset
+    /// threshold, and `power_up_ms` should cover the ramp of the supply. A
+    /// missing acknowledge while polling is treated as not ready yet rather
+    /// than as a bus error, so a short `power_up_ms` only costs polls.
+    /// Returns [`Error::Pin`] if the pin can't be driven and
+    /// [`Error::Timeout`] if the chip doesn't become ready.
+    ///
+    /// All registers return to their default values, which the cached sensor
+    /// configuration is reset to as well.
+    pub fn hardware_reset<D: embedded_hal::delay::DelayNs>(
+        &mut self,
+        delay: &mut D,
+        off_time_ms: u32,
+        power_up_ms: u32,
+    ) -> Result<(), Error<E>> {
+        self.reset.set_low().map_err(|_| Error::Pin)?;
+        delay.delay_ms(off_time_ms);
+        self.reset.set_high().map_err(|_| Error::Pin)?;
+        delay.delay_ms(power_up_ms);
+        self.reset_state();
+
+        for _ in 0..MAX_POLLS {
+            match self.read_register(Register::Status) {
+                Ok(sr) if sr & CHIP_READY != 0 => return Ok(()),
+                Ok(_) => {}
+                // The device doesn't acknowledge until it has powered up.
+                Err(Error::I2c(e)) if is_no_acknowledge::<I2C>(&e) => {}
+                Err(e) => return Err(e),
+            }
+            delay.delay_us(POLL_INTERVAL_US);
         }
+        Err(Error::Timeout)
     }
+}
 
+impl<I2C, E, MODE: Mode, RST> Ldc3114<I2C, MODE, RST>
+where
+    I2C: embedded_hal::i2c::I2c + embedded_hal::i2c::ErrorType<Error = E>,
+{
     /// Reads the device ID.
-    pub fn read_device_id(&mut self) -> Result<u8, Error<E>> {
-        self.read_register(Register::DeviceIdMsb)
+    ///
+    /// Compare the result with [`DEVICE_ID`] to check for the right part.
+    pub fn read_device_id(&mut self) -> Result<u16, Error<E>> {
+        let mut buffer = [0; 2];
+        self.i2c
+            .write_read(I2C_ADDR, &[Register::DeviceIdLsb.addr()], &mut buffer)
+            .map_err(Error::I2c)?;
+
+        let data = u16::from_le_bytes(buffer);
+        Ok(data)
     }
 
     /// Reads the manufacturer ID.
+    ///
+    /// Compare the result with [`MANUFACTURER_ID`] to check for a Texas
+    /// Instruments device.
     pub fn read_manufacturer_id(&mut self) -> Result<u16, Error<E>> {
         let mut buffer = [0; 2];
         self.i2c
@@ -32,20 +126,87 @@ where
         Ok(data)
     }
 
+    /// Reads the manufacturer and device IDs in a single transaction, packed
+    /// with the manufacturer ID in the high half.
+    ///
+    /// Compare the result with [`EXPECTED_FULL_ID`] for a one-shot identity
+    /// check.
+    pub fn full_id(&mut self) -> Result<u32, Error<E>> {
+        let mut buffer = [0; 4];
+        self.i2c
+            .write_read(I2C_ADDR, &[Register::ManufacturerIdLsb.addr()], &mut buffer)
+            .map_err(Error::I2c)?;
+
+        let manufacturer_id = u16::from_le_bytes([buffer[0], buffer[1]]);
+        let device_id = u16::from_le_bytes([buffer[2], buffer[3]]);
+        Ok((u32::from(manufacturer_id) << 16) | u32::from(device_id))
+    }
+
+    /// Verifies that the device at the LDC3114 address is an LDC3114.
+    ///
+    /// Returns [`Error::UnexpectedDevice`] with the IDs that were read if they
+    /// don't match [`MANUFACTURER_ID`] and [`DEVICE_ID`], which usually
+    /// indicates an address conflict with another device on the bus or a
+    /// different part fitted to the footprint.
+    pub fn verify_device(&mut self) -> Result<(), Error<E>> {
+        let id = self.full_id()?;
+        check_full_id(id)
+    }
+
+    /// Checks that an LDC3114 is present on the bus, like
+    /// [`Self::verify_device`].
+    ///
+    /// Returns [`Error::NotPresent`] if the address isn't acknowledged, so
+    /// that an absent device can be told apart from other bus errors, which
+    /// are returned as [`Error::I2c`].
+    pub fn probe(&mut self) -> Result<(), Error<E>> {
+        match self.verify_device() {
+            Err(Error::I2c(e)) if is_no_acknowledge::<I2C>(&e) => Err(Error::NotPresent),
+            result => result,
+        }
+    }
+
+    /// Like [`Self::verify_device`], but reads the manufacturer and device IDs
+    /// twice and requires both reads to agree.
+    ///
+    /// Returns [`Error::UnstableRead`] if they disagree, so that a flaky bus
+    /// isn't mistaken for an unexpected device.
+    pub fn verify_device_double_read(&mut self) -> Result<(), Error<E>> {
+        let first = self.full_id()?;
+        let second = self.full_id()?;
+        if first != second {
+            return Err(Error::UnstableRead);
+        }
+
+        check_full_id(first)
+    }
+
     /// Reads the status register.
+    ///
+    /// Note that a single read consumes all clear-on-read flags at once, i.e.
+    /// OUT_STATUS, MAXOUT, FSM_WD, LC_WD, TIMEOUT and REGISTER_FLAG. They are
+    /// kept in the status accumulated by the driver, see
+    /// [`Self::accumulated_status`].
     pub fn read_status(&mut self) -> Result<Status, Error<E>> {
         let sr = self.read_register(Register::Status)?;
+        Ok(Status::from_bits(sr))
+    }
 
-        Ok(Status {
-            output_status: (sr & OUT_STATUS != 0),
-            chip_ready: (sr & CHIP_READY != 0),
-            ready_to_write: (sr & RDY_TO_WRITE != 0),
-            maximum_output_code: (sr & MAXOUT != 0),
-            fsm_watchdog_error: (sr & FSM_WD != 0),
-            lc_sensor_watchdog_error: (sr & LC_WD != 0),
-            button_timeout: (sr & TIMEOUT != 0),
-            register_integrity_bad: (sr & REGISTER_FLAG != 0),
-        })
+    /// Reads the status register, returning both its raw value and the
+    /// decoded flags.
+    pub fn read_status_raw(&mut self) -> Result<(u8, Status), Error<E>> {
+        let sr = self.read_register(Register::Status)?;
+        Ok((sr, Status::from_bits(sr)))
+    }
+
+    /// Clears STATUS:OUT_STATUS by reading the status register.
+    ///
+    /// As reading the status register also clears the error flags, they are
+    /// merged into `acc` so they aren't lost.
+    pub fn clear_output_status(&mut self, acc: &mut StatusAccumulator) -> Result<(), Error<E>> {
+        let sr = self.read_register(Register::Status)?;
+        acc.accumulate(sr);
+        Ok(())
     }
 
     /// Checks if the registers are ready to be written.
@@ -62,263 +223,998 @@ where
         Ok(is_ready)
     }
 
-    /// Resets the device and register configurations.
+    /// Waits until the registers are ready to be written.
     ///
-    /// All registers will be returned to default values.
-    /// Normal operation will not resume until STATUS:CHIP_READY=1.
-    pub fn full_reset(&mut self) -> Result<(), Error<E>> {
-        self.write_register(Register::Reset, FULL_RESET)
+    /// Returns [`Error::Timeout`] if STATUS:RDY_TO_WRITE is not set within
+    /// a bounded number of polls.
+    pub fn wait_for_ready_to_write<D: embedded_hal::delay::DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        self.poll_register_bits(Register::Status, RDY_TO_WRITE, delay)
+    }
+
+    /// Waits until the chip is ready after internal reset.
+    ///
+    /// Returns [`Error::Timeout`] if STATUS:CHIP_READY is not set within
+    /// a bounded number of polls.
+    pub fn wait_for_chip_ready<D: embedded_hal::delay::DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        self.poll_register_bits(Register::Status, CHIP_READY, delay)
     }
 
-    /// Enter configuration mode.
+    /// Waits until new data is available, i.e. OUT:DATA_RDY is set, reading
+    /// the OUT register every `poll_interval_us` microseconds.
     ///
-    /// Any device configuration changes should be made in this mode.
-    pub fn config_mode(&mut self) -> Result<(), Error<E>> {
-        self.write_register(Register::Reset, CONFIG_MODE)
+    /// This doesn't rely on the INTB pin. Returns [`Error::Timeout`] if no
+    /// new data is reported within two seconds, longer than the period of the
+    /// slowest scan rate, so a dead sensor doesn't block forever. Like
+    /// [`Self::is_data_ready`], this clears DATA_RDY.
+    pub fn wait_for_data_ready<D: embedded_hal::delay::DelayNs>(
+        &mut self,
+        delay: &mut D,
+        poll_interval_us: u32,
+    ) -> Result<(), Error<E>> {
+        let max_polls = DATA_READY_TIMEOUT_US / poll_interval_us.max(1);
+        for _ in 0..=max_polls {
+            if self.is_data_ready()? {
+                return Ok(());
+            }
+            delay.delay_us(poll_interval_us);
+        }
+        Err(Error::Timeout)
+    }
+
+    /// Polls a register until any of the given bits is set.
+    fn poll_register_bits<D: embedded_hal::delay::DelayNs>(
+        &mut self,
+        register: Register,
+        bits: u8,
+        delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        for _ in 0..MAX_POLLS {
+            let value = self.read_register(register)?;
+            if value & bits != 0 {
+                return Ok(());
+            }
+            delay.delay_us(POLL_INTERVAL_US);
+        }
+        Err(Error::Timeout)
     }
 
-    /// Enter normal mode (exit configuration mode).
-    pub fn normal_mode(&mut self) -> Result<(), Error<E>> {
+    /// Reads the sensor configuration needed by derived calculations from
+    /// the device and updates the cached values.
+    pub fn refresh_cache(&mut self) -> Result<(), Error<E>> {
         let lcdiv = self.read_register(Register::LcDivider)?;
+        let np_scan_rate = self.read_register(Register::NpScanRate)?;
         let scfg0 = self.read_register(Register::Sensor0Config)?;
         let scfg1 = self.read_register(Register::Sensor1Config)?;
         let scfg2 = self.read_register(Register::Sensor2Config)?;
         let scfg3 = self.read_register(Register::Sensor3Config)?;
 
-        self.lcdiv = lcdiv & 0x07;
-        self.sency0 = scfg0 & 0x1F;
-        self.sency1 = scfg1 & 0x1F;
-        self.sency2 = scfg2 & 0x1F;
-        self.sency3 = scfg3 & 0x1F;
+        self.state.lcdiv = LC_DIVIDER.get(lcdiv);
+        self.state.scan_rate = NP_SCAN_RATE.get(np_scan_rate);
+        self.state.sency0 = SENCY.get(scfg0);
+        self.state.sency1 = SENCY.get(scfg1);
+        self.state.sency2 = SENCY.get(scfg2);
+        self.state.sency3 = SENCY.get(scfg3);
+        self.state.cache_valid = true;
+        Ok(())
+    }
 
-        self.write_register(Register::Reset, 0)
+    /// Checks if new data is available, i.e. OUT:DATA_RDY is set.
+    ///
+    /// Note that reading the OUT register clears DATA_RDY, so a second call
+    /// returns `false` until the next conversion completes.
+    pub fn is_data_ready(&mut self) -> Result<bool, Error<E>> {
+        let out = self.read_register(Register::Out)?;
+        Ok(out & DATA_RDY != 0)
+    }
+
+    /// Reads the OUT register and decodes it into the channel output logic
+    /// states.
+    ///
+    /// This is the same as [`Self::read_output_logic_states`]. Note that
+    /// reading the OUT register clears DATA_RDY.
+    pub fn read_output_states(&mut self) -> Result<OutputLogicStates, Error<E>> {
+        self.read_output_logic_states()
+    }
+
+    /// Checks if the button output of the given channel is asserted, as
+    /// reported by its OUT register bit.
+    ///
+    /// Note that reading the OUT register clears DATA_RDY.
+    pub fn is_button_pressed<T: ChannelRegisters>(&mut self, _ch: T) -> Result<bool, Error<E>> {
+        let out = self.read_register(Register::Out)?;
+        Ok(OutputLogicStates::from_bits(out).outputs()[T::CH as usize])
+    }
+
+    /// Reads the button outputs of all channels at once, indexed by channel
+    /// number.
+    ///
+    /// A single read of the OUT register gives a consistent snapshot of
+    /// simultaneously asserted buttons. Note that it clears DATA_RDY.
+    pub fn pressed_channels(&mut self) -> Result<[bool; 4], Error<E>> {
+        let out = self.read_register(Register::Out)?;
+        Ok(OutputLogicStates::from_bits(out).outputs())
+    }
+
+    /// Reads the button outputs of all channels at once and debounces them
+    /// with `scanner`.
+    ///
+    /// Note that reading the OUT register clears DATA_RDY.
+    pub fn scan_buttons(&mut self, scanner: &mut ButtonScanner) -> Result<ButtonScan, Error<E>> {
+        let outputs = self.pressed_channels()?;
+        Ok(scanner.update(outputs))
     }
 
     /// Reads the channel output logic states.
     pub fn read_output_logic_states(&mut self) -> Result<OutputLogicStates, Error<E>> {
         let out = self.read_register(Register::Out)?;
 
-        Ok(OutputLogicStates {
-            new_data_available: (out & DATA_RDY != 0),
-            out0: (out & OUT0 != 0),
-            out1: (out & OUT1 != 0),
-            out2: (out & OUT2 != 0),
-            out3: (out & OUT3 != 0),
-        })
+        Ok(OutputLogicStates::from_bits(out))
+    }
+
+    /// Reads the processed data of the given channel and classifies it as
+    /// pressed or not with a software threshold.
+    ///
+    /// This is meant for detecting presses in firmware instead of with the
+    /// button algorithm of the device. The channel becomes pressed once its
+    /// data reaches `threshold` and released once the data drops below
+    /// `threshold - hysteresis`. The state of each channel is kept by the
+    /// driver between calls.
+    pub fn read_press_state<T: ChannelRegisters>(
+        &mut self,
+        ch: T,
+        threshold: i16,
+        hysteresis: i16,
+    ) -> Result<bool, Error<E>> {
+        let data = self.read_button_data(ch)?;
+        let bit = 1 << T::CH;
+        let pressed = press_state(
+            self.state.press_states & bit != 0,
+            data,
+            threshold,
+            hysteresis,
+        );
+        if pressed {
+            self.state.press_states |= bit;
+        } else {
+            self.state.press_states &= !bit;
+        }
+        Ok(pressed)
+    }
+
+    /// Checks if the baseline tracking of the given channel is currently
+    /// frozen.
+    ///
+    /// The baseline is frozen while the channel's baseline tracking pause is
+    /// enabled and its output is asserted, which explains processed data that
+    /// doesn't return to zero.
+    pub fn is_baseline_frozen<T: ChannelRegisters>(&mut self, _ch: T) -> Result<bool, Error<E>> {
+        let btpause = self.read_register(Register::BtPauseMaxWin)?;
+        let out = self.read_register(Register::Out)?;
+        let asserted = OutputLogicStates::from_bits(out).outputs()[T::CH as usize];
+        Ok(btpause & T::BTPAUSE_BIT != 0 && asserted)
+    }
+
+    /// Reads the STATUS and OUT registers in a single transaction.
+    ///
+    /// The returned [`Event`] can be queued and decoded later.
+    pub fn read_event(&mut self) -> Result<Event, Error<E>> {
+        let mut buffer = [0; 2];
+        self.i2c
+            .write_read(I2C_ADDR, &[Register::Status.addr()], &mut buffer)
+            .map_err(Error::I2c)?;
+
+        self.latch_status(buffer[0]);
+        Ok(Event::from_bytes(buffer))
     }
 
     /// Reads the button data for the given channel.
+    ///
+    /// This is the same as [`Self::read_data`].
     pub fn read_button_data(&mut self, ch: impl ChannelRegisters) -> Result<i16, Error<E>> {
+        self.read_data(ch)
+    }
+
+    /// Reads the processed data of the given channel.
+    ///
+    /// The DATA registers hold a signed 12-bit value from -0x800 to +0x7FF,
+    /// which is sign-extended to an `i16`. Both registers are read in a
+    /// single transaction, LSB first, so the value can't be torn across two
+    /// conversions.
+    pub fn read_data<T: ChannelRegisters>(&mut self, ch: T) -> Result<i16, Error<E>> {
         let mut buffer = [0; 2];
         self.i2c
             .write_read(I2C_ADDR, &[ch.data_lsb() as u8], &mut buffer)
             .map_err(Error::I2c)?;
 
-        let data = i16::from_le_bytes(buffer);
-        Ok(data)
+        Ok(button_data_from_bytes(buffer[0], buffer[1]))
     }
 
-    /// Reads the pre-processed raw sensor data for the given channel.
+    /// Reads the processed data of the given channel scaled to
+    /// `-full_scale..=full_scale`.
     ///
-    /// The value returned is given by the following formula:
-    /// ```
-    /// f_sensor = 30 * W * 44_000_000 / raw_data
-    /// ```
-    /// where
-    /// ```
-    /// W = 128 * (1 + SENCY_n) * (2^LCDIV)
-    /// ```
-    pub fn read_raw_data<T: ChannelRegisters>(&mut self, ch: T) -> Result<u32, Error<E>> {
-        let mut buffer = [0; 4];
-        let slice = &mut buffer[0..=2];
+    /// The code range is mapped linearly, with -0x800 mapped to `-full_scale`
+    /// and +0x7FF to `full_scale`, using integer arithmetic that can't
+    /// overflow. `full_scale` must be positive, otherwise
+    /// [`Error::InvalidParameter`] is returned without touching the bus.
+    pub fn read_data_scaled<T: ChannelRegisters>(
+        &mut self,
+        ch: T,
+        full_scale: i32,
+    ) -> Result<i32, Error<E>> {
+        if full_scale <= 0 {
+            return Err(Error::InvalidParameter);
+        }
+        let data = i64::from(self.read_data(ch)?);
+        let max_code = if data < 0 { 0x800 } else { 0x7FF };
+        Ok((data * i64::from(full_scale) / max_code) as i32)
+    }
+
+    /// Reads the processed data of all channels in a single transaction.
+    ///
+    /// The values are sign-extended like with [`Self::read_data`] and
+    /// indexed by channel number.
+    pub fn read_all_data(&mut self) -> Result<[i16; 4], Error<E>> {
+        let mut buffer = [0; 8];
         self.i2c
-            .write_read(I2C_ADDR, &[ch.raw_data_lsb() as u8], slice)
+            .write_read(I2C_ADDR, &[Register::Data0Lsb.addr()], &mut buffer)
             .map_err(Error::I2c)?;
 
-        let data = u32::from_le_bytes(buffer);
-        if data == 0 {
-            return Ok(0);
-        }
+        Ok(core::array::from_fn(|ch| {
+            button_data_from_bytes(buffer[2 * ch], buffer[2 * ch + 1])
+        }))
+    }
+
+    /// Reads the button data for the given channel along with its age.
+    ///
+    /// OUT:DATA_RDY is read (and thereby cleared) first, and if it is set,
+    /// `now` is recorded in `tracker` as the time of the latest new data.
+    /// The returned age is the time elapsed since then, or `None` if no new
+    /// data has been reported yet. An ever-growing age indicates that the
+    /// device stopped converting.
+    pub fn read_data_with_age<C, T, D>(
+        &mut self,
+        ch: C,
+        now: T,
+        tracker: &mut DataAgeTracker<T>,
+    ) -> Result<(i16, Option<D>), Error<E>>
+    where
+        C: ChannelRegisters,
+        T: Copy + core::ops::Sub<Output = D>,
+    {
+        let out = self.read_register(Register::Out)?;
+        let data = self.read_button_data(ch)?;
+        Ok((data, tracker.update(out & DATA_RDY != 0, now)))
+    }
+
+    /// Reads the processed data and button state of all channels.
+    ///
+    /// The STATUS, OUT and DATA registers are read in a single transaction,
+    /// so the returned states are coherent. Note that this clears the
+    /// clear-on-read flags of the STATUS and OUT registers.
+    pub fn measure_all(&mut self) -> Result<[ChannelState; 4], Error<E>> {
+        let mut buffer = [0; 10];
+        self.measure_all_with_buffer(&mut buffer)
+    }
+
+    /// Same as [`Self::measure_all`], using the given scratch buffer.
+    ///
+    /// `buffer` must be at least 10 bytes long, otherwise
+    /// [`Error::InvalidParameter`] is returned.
+    pub fn measure_all_with_buffer(
+        &mut self,
+        buffer: &mut [u8],
+    ) -> Result<[ChannelState; 4], Error<E>> {
+        let buffer = buffer.get_mut(..10).ok_or(Error::InvalidParameter)?;
+        self.i2c
+            .write_read(I2C_ADDR, &[Register::Status.addr()], buffer)
+            .map_err(Error::I2c)?;
 
-        let sency = match T::CH {
-            0 => self.sency0,
-            1 => self.sency1,
-            2 => self.sency2,
-            3 => self.sency3,
-            _ => unreachable!(),
+        self.latch_status(buffer[0]);
+        let out = buffer[1];
+        let state = |lsb: usize, bit: u8| ChannelState {
+            data: button_data_from_bytes(buffer[lsb], buffer[lsb + 1]),
+            pressed: out & bit != 0,
         };
 
-        let w = 128 * (1 + sency as u32) * (2 << self.lcdiv as u32);
-        let fsensor = 30 * w as u64 * 44_000_000 / data as u64;
-        Ok(fsensor as u32)
+        Ok([
+            state(2, OUT0),
+            state(4, OUT1),
+            state(6, OUT2),
+            state(8, OUT3),
+        ])
     }
 
-    /// Sets up the entire device configuration.
-    pub fn set_device_configuration(&mut self, config: &DeviceConfig) -> Result<(), Error<E>> {
-        fn en_bits<T: ChannelRegisters>(_ch: T, mode: ChannelMode) -> u8 {
-            match mode {
-                ChannelMode::Disabled => 0x00,
-                ChannelMode::NormalMode => T::EN_BIT,
-                ChannelMode::NormalAndLowPowerMode => T::EN_BIT | T::LPEN_BIT,
-            }
+    /// Measures statistics of the processed data of the given channel.
+    ///
+    /// `samples` new data samples are collected, waiting for OUT:DATA_RDY
+    /// before each of them with [`Self::wait_for_data_ready`], so even the
+    /// slowest scan rates in normal and low power mode are covered.
+    ///
+    /// To estimate the signal-to-noise ratio of a channel, measure once
+    /// without touching the sensor and once while pressing it, then compare
+    /// both measurements with [`SignalStats::snr`].
+    pub fn measure_signal_stats<T, D>(
+        &mut self,
+        ch: T,
+        samples: u16,
+        delay: &mut D,
+    ) -> Result<SignalStats, Error<E>>
+    where
+        T: ChannelRegisters,
+        D: embedded_hal::delay::DelayNs,
+    {
+        if samples == 0 {
+            return Err(Error::InvalidParameter);
         }
 
-        fn btpause_maxwin_bits<T: ChannelRegisters>(_ch: T, btpause: bool, maxwin: bool) -> u8 {
-            match (btpause, maxwin) {
-                (false, false) => 0x00,
-                (true, false) => T::BTPAUSE_BIT,
-                (false, true) => T::MAXWIN_BIT,
-                (true, true) => T::BTPAUSE_BIT | T::MAXWIN_BIT,
-            }
+        let mut sum = 0i32;
+        let mut min = i16::MAX;
+        let mut max = i16::MIN;
+        for _ in 0..samples {
+            self.wait_for_data_ready(delay, POLL_INTERVAL_US)?;
+            let data = self.read_button_data(ch)?;
+            sum += data as i32;
+            min = min.min(data);
+            max = max.max(data);
         }
 
-        fn common_deform_bits<T: ChannelRegisters>(_ch: T, common: bool, deform: bool) -> u8 {
-            match (common, deform) {
-                (false, false) => 0x00,
-                (true, false) => T::ANTICOM_BIT,
-                (false, true) => T::ANTIDFORM_BIT,
-                (true, true) => T::ANTICOM_BIT | T::ANTIDFORM_BIT,
-            }
-        }
+        Ok(SignalStats {
+            mean: (sum / samples as i32) as i16,
+            min,
+            max,
+        })
+    }
+
+    /// Reads the processed data of all channels once and adds it to
+    /// `measurement`.
+    ///
+    /// Call this whenever new data is available, e.g. after
+    /// [`Self::wait_for_data_ready`], until enough samples were collected.
+    pub fn accumulate_noise_sample(
+        &mut self,
+        measurement: &mut NoiseMeasurement,
+    ) -> Result<(), Error<E>> {
+        let data = self.read_all_data()?;
+        measurement.add_sample(data);
+        Ok(())
+    }
+
+    /// Reads the 24-bit pre-processed raw sensor data for the given channel.
+    ///
+    /// The three RAW_DATA registers of a channel are read in a single
+    /// transaction, so the value can't be torn across two conversions.
+    /// Despite its name, RAW_DATAn_3 sits at the lowest address and holds
+    /// the most significant byte, followed by RAW_DATAn_2 and RAW_DATAn_1
+    /// with the least significant byte.
+    ///
+    /// Unlike the processed data returned by [`Self::read_data`], the raw
+    /// data is an unsigned 24-bit magnitude and must not be sign-extended.
+    pub fn read_raw_data<T: ChannelRegisters>(&mut self, ch: T) -> Result<u32, Error<E>> {
+        let mut buffer = [0; 3];
+        self.i2c
+            .write_read(I2C_ADDR, &[ch.raw_data_start() as u8], &mut buffer)
+            .map_err(Error::I2c)?;
+        Ok(raw_data_from_bytes(buffer))
+    }
 
-        fn opol_dpol_bits<T: ChannelRegisters>(
-            _ch: T,
-            opol: OutputPolarity,
-            dpol: DataPolarity,
-        ) -> u8 {
-            match (opol, dpol) {
-                (OutputPolarity::ActiveLow, DataPolarity::Inverted) => 0x00,
-                (OutputPolarity::ActiveHigh, DataPolarity::Inverted) => T::OPOL_BIT,
-                (OutputPolarity::ActiveLow, DataPolarity::Normal) => T::DPOL_BIT,
-                (OutputPolarity::ActiveHigh, DataPolarity::Normal) => T::OPOL_BIT | T::DPOL_BIT,
+    /// Checks if the device is actively scanning the given channel, i.e. its
+    /// raw data changes over time.
+    ///
+    /// The raw data is re-read at the polling interval until it differs from
+    /// the first read, for at most the polling budget. Note that a perfectly
+    /// static sensor could read identical values and be reported as not
+    /// scanning, although conversion noise makes this unlikely in practice.
+    pub fn is_scanning<T, D>(&mut self, ch: T, delay: &mut D) -> Result<bool, Error<E>>
+    where
+        T: ChannelRegisters,
+        D: embedded_hal::delay::DelayNs,
+    {
+        let first = self.read_raw_data(ch)?;
+        for _ in 0..MAX_POLLS {
+            delay.delay_us(POLL_INTERVAL_US);
+            if self.read_raw_data(ch)? != first {
+                return Ok(true);
             }
         }
+        Ok(false)
+    }
 
-        let mut en = en_bits(Channel0, config.ch0.mode);
-        en |= en_bits(Channel1, config.ch1.mode);
-        en |= en_bits(Channel2, config.ch2.mode);
-        en |= en_bits(Channel3, config.ch3.mode);
-        self.write_register(Register::En, en)?;
-
-        self.set_channel_gain(Channel0, config.ch0.gain)?;
-        self.set_channel_gain(Channel1, config.ch1.gain)?;
-        self.set_channel_gain(Channel2, config.ch2.gain)?;
-        self.set_channel_gain(Channel3, config.ch3.gain)?;
+    /// Reads the raw data of all channels in a single transaction.
+    ///
+    /// The values are assembled like with [`Self::read_raw_data`] and indexed
+    /// by channel number.
+    pub fn read_all_raw_data(&mut self) -> Result<[u32; 4], Error<E>> {
+        let mut buffer = [0; 12];
+        self.i2c
+            .write_read(I2C_ADDR, &[Register::RawData0_3.addr()], &mut buffer)
+            .map_err(Error::I2c)?;
 
-        self.set_normal_scan_rate(config.scan_rate)?;
-        self.set_low_power_scan_rate(config.low_power_scan_rate)?;
+        Ok(core::array::from_fn(|ch| {
+            raw_data_from_bytes([buffer[3 * ch], buffer[3 * ch + 1], buffer[3 * ch + 2]])
+        }))
+    }
 
-        let mut intpol = (config.enable_reset_of_button_baseline_tracking as u8) << 4;
-        intpol |= (config.enable_button_press_detection_algorithm as u8) << 3;
-        intpol |= (config.interrupt_polarity as u8) << 2;
-        intpol |= ((!config.enable_button_timeout) as u8) << 1;
-        intpol |= (!config.enable_max_out_check) as u8;
-        self.write_register(Register::IntPol, intpol)?;
+    /// Reads the raw data of the given channel and converts it to the sensor
+    /// frequency in Hz.
+    ///
+    /// The value returned is given by the following formula:
+    /// ```text
+    /// f_sensor = 30 * W * 44_000_000 / raw_data
+    /// ```
+    /// where
+    /// ```text
+    /// W = 128 * (1 + SENCY_n) * (2^LCDIV)
+    /// ```
+    ///
+    /// Returns [`Error::CacheInvalidated`] if the cached sensor configuration
+    /// is unknown.
+    pub fn read_sensor_frequency<T: ChannelRegisters>(&mut self, ch: T) -> Result<u32, Error<E>> {
+        if !self.state.cache_valid {
+            return Err(Error::CacheInvalidated);
+        }
 
-        self.set_baseline_tracking_increment_np(config.baseline_tracking_increment_np)?;
-        self.set_baseline_tracking_increment_lp(config.baseline_tracking_increment_lp)?;
+        let data = self.read_raw_data(ch)?;
+        self.sensor_frequency_hz(ch, data, REFERENCE_CLOCK_HZ)
+            .ok_or(Error::CacheInvalidated)
+    }
 
-        let mut btpause_maxwin = btpause_maxwin_bits(
-            Channel0,
-            config.ch0.baseline_tracking_pause,
-            config.ch0.enable_max_win_button_algorithm,
-        );
-        btpause_maxwin |= btpause_maxwin_bits(
-            Channel1,
-            config.ch1.baseline_tracking_pause,
-            config.ch1.enable_max_win_button_algorithm,
-        );
-        btpause_maxwin |= btpause_maxwin_bits(
-            Channel2,
-            config.ch2.baseline_tracking_pause,
-            config.ch2.enable_max_win_button_algorithm,
-        );
-        btpause_maxwin |= btpause_maxwin_bits(
-            Channel3,
-            config.ch3.baseline_tracking_pause,
-            config.ch3.enable_max_win_button_algorithm,
-        );
-        self.write_register(Register::BtPauseMaxWin, btpause_maxwin)?;
+    /// Reads the raw data of the given channel like [`Self::read_raw_data`],
+    /// retrying up to `retries` times while it is zero.
+    ///
+    /// A raw data of zero usually means that the read caught the device
+    /// mid-conversion or before the oscillator stabilized, so each retry is
+    /// made after a delay of one polling interval. Zero can also be
+    /// legitimate, e.g. for a channel whose oscillator doesn't start, in
+    /// which case the last zero is returned once the retries are exhausted.
+    pub fn read_raw_data_settled<T, D>(
+        &mut self,
+        ch: T,
+        delay: &mut D,
+        retries: u8,
+    ) -> Result<u32, Error<E>>
+    where
+        T: ChannelRegisters,
+        D: embedded_hal::delay::DelayNs,
+    {
+        let mut data = self.read_raw_data(ch)?;
+        for _ in 0..retries {
+            if data != 0 {
+                break;
+            }
+            delay.delay_us(POLL_INTERVAL_US);
+            data = self.read_raw_data(ch)?;
+        }
+        Ok(data)
+    }
 
-        self.set_lc_divider(config.lc_divider)?;
-        self.set_hysteresis(config.hysteresis)?;
-        self.set_antitwist(config.antitwist)?;
+    /// Checks the health of the given channel.
+    ///
+    /// The channel is reported as oscillating if its raw data is nonzero, and
+    /// as railed if its processed data is at either end of its range.
+    pub fn channel_health<T: ChannelRegisters>(
+        &mut self,
+        ch: T,
+    ) -> Result<ChannelHealth, Error<E>> {
+        let en = self.read_register(Register::En)?;
+        let raw = self.read_raw_data(ch)?;
+        let data = self.read_button_data(ch)?;
+
+        Ok(ChannelHealth {
+            enabled: en & T::EN_BIT != 0,
+            oscillating: raw != 0,
+            railed: matches!(data, 0x7FF | -0x800),
+        })
+    }
 
-        let mut common_deform = common_deform_bits(
-            Channel0,
-            config.ch0.enable_anticommon_algorithm,
-            config.ch0.enable_antideform_algorithm,
-        );
-        common_deform |= common_deform_bits(
-            Channel1,
-            config.ch1.enable_anticommon_algorithm,
-            config.ch1.enable_antideform_algorithm,
-        );
-        common_deform |= common_deform_bits(
-            Channel2,
-            config.ch2.enable_anticommon_algorithm,
-            config.ch2.enable_antideform_algorithm,
-        );
-        common_deform |= common_deform_bits(
-            Channel3,
-            config.ch3.enable_anticommon_algorithm,
-            config.ch3.enable_antideform_algorithm,
-        );
-        self.write_register(Register::CommonDeform, common_deform)?;
+    /// Reads the EN register, so that the enabled channels can be restored
+    /// with [`restore_enable_state`](Self::restore_enable_state) later.
+    pub fn save_enable_state(&mut self) -> Result<u8, Error<E>> {
+        self.read_register(Register::En)
+    }
 
-        let mut opol_dpol = opol_dpol_bits(
-            Channel0,
-            config.ch0.output_polarity,
-            config.ch0.data_polarity,
-        );
-        opol_dpol |= opol_dpol_bits(
-            Channel1,
-            config.ch1.output_polarity,
-            config.ch1.data_polarity,
-        );
-        opol_dpol |= opol_dpol_bits(
-            Channel2,
-            config.ch2.output_polarity,
-            config.ch2.data_polarity,
-        );
-        opol_dpol |= opol_dpol_bits(
-            Channel3,
-            config.ch3.output_polarity,
-            config.ch3.data_polarity,
-        );
-        self.write_register(Register::OpolDpol, opol_dpol)?;
+    /// Reads the gain of the given channel.
+    ///
+    /// Reserved upper bits are masked off, so the result is in `0..=0x3F`.
+    pub fn get_gain<T: ChannelRegisters>(&mut self, ch: T) -> Result<u8, Error<E>> {
+        let gain = self.read_register(ch.gain())?;
+        Ok(GAIN.get(gain))
+    }
 
-        let mut cntsc = (config.ch3.counter_scale as u8) << 6;
-        cntsc |= (config.ch2.counter_scale as u8) << 4;
-        cntsc |= (config.ch1.counter_scale as u8) << 2;
-        cntsc |= config.ch1.counter_scale as u8;
-        self.write_register(Register::Cntsc, cntsc)?;
+    /// Reads the gain of all channels.
+    ///
+    /// Reserved upper bits are masked off like with [`Self::get_gain`].
+    pub fn read_gains(&mut self) -> Result<Gains, Error<E>> {
+        let mut buffer = [0; 7];
+        self.read_gains_with_buffer(&mut buffer)
+    }
 
-        self.set_sensor_config(Channel0, &config.ch0.sensor_config)?;
-        self.set_sensor_config(Channel1, &config.ch1.sensor_config)?;
-        self.set_sensor_config(Channel2, &config.ch2.sensor_config)?;
-        self.set_sensor_config(Channel3, &config.ch3.sensor_config)?;
+    /// Reads the gain of all channels using the given scratch buffer.
+    ///
+    /// Reserved upper bits are masked off like with [`Self::get_gain`].
+    /// `buffer` must be at least 7 bytes long, otherwise
+    /// [`Error::InvalidParameter`] is returned.
+    pub fn read_gains_with_buffer(&mut self, buffer: &mut [u8]) -> Result<Gains, Error<E>> {
+        let buffer = buffer.get_mut(..7).ok_or(Error::InvalidParameter)?;
+        self.i2c
+            .write_read(I2C_ADDR, &[Register::Gain0.addr()], buffer)
+            .map_err(Error::I2c)?;
 
-        self.set_fast_tracking_factor(Channel0, config.ch0.fast_tracking_factor)?;
-        self.set_fast_tracking_factor(Channel3, config.ch3.fast_tracking_factor)?;
+        Ok(Gains {
+            ch0: GAIN.get(buffer[0]),
+            ch1: GAIN.get(buffer[2]),
+            ch2: GAIN.get(buffer[4]),
+            ch3: GAIN.get(buffer[6]),
+        })
+    }
 
-        let mut ftf1_2 = (config.ch2.fast_tracking_factor as u8) << 6;
-        ftf1_2 |= (config.ch1.fast_tracking_factor as u8) << 4;
-        self.write_register(Register::Ftf1_2, ftf1_2)?;
+    /// Reads the event that drives pin INTB.
+    pub fn read_interrupt_source(&mut self) -> Result<InterruptSource, Error<E>> {
+        let intpol = self.read_register(Register::IntPol)?;
+        if intpol & BTN_ALG_EN != 0 {
+            Ok(InterruptSource::ButtonChange)
+        } else {
+            Ok(InterruptSource::DataReady)
+        }
+    }
 
-        Ok(())
+    /// Reads the Fast Tracking Factor (FTF) of all channels.
+    pub fn read_all_ftf(&mut self) -> Result<[FastTrackingFactor; 4], Error<E>> {
+        let ftf0 = self.read_register(Register::Ftf0)?;
+        let ftf1_2 = self.read_register(Register::Ftf1_2)?;
+        let ftf3 = self.read_register(Register::Ftf3)?;
+
+        Ok([
+            FastTrackingFactor::from_register(ftf0, ftf_field::<Channel0>()),
+            FastTrackingFactor::from_register(ftf1_2, ftf_field::<Channel1>()),
+            FastTrackingFactor::from_register(ftf1_2, ftf_field::<Channel2>()),
+            FastTrackingFactor::from_register(ftf3, ftf_field::<Channel3>()),
+        ])
     }
 
-    /// Configures a given channel.
-    pub fn configure_channel<T: ChannelRegisters>(
+    /// Reads the contiguous block of configuration registers from EN (0x0C)
+    /// to CNTSC (0x1E) into `out`, in ascending address order.
+    ///
+    /// This is meant to be attached to bug reports, and can be decoded
+    /// offline with [`config_block_value`].
+    pub fn read_raw_config_block(
         &mut self,
-        ch: T,
-        config: &ChannelConfig,
+        out: &mut [u8; CONFIG_BLOCK_LEN],
     ) -> Result<(), Error<E>> {
-        self.set_channel_mode(ch, config.mode)?;
-        self.set_channel_gain(ch, config.gain)?;
-        self.set_output_polarity(ch, config.output_polarity)?;
-        self.set_counter_scale(ch, config.counter_scale)?;
+        self.i2c
+            .write_read(I2C_ADDR, &[Register::En.addr()], out)
+            .map_err(Error::I2c)
+    }
+
+    /// Reads all configuration registers into a snapshot.
+    ///
+    /// The snapshot can later be compared against the device with
+    /// [`Self::check_against_golden`] to detect silent register corruption.
+    pub fn capture_golden(&mut self) -> Result<GoldenSnapshot, Error<E>> {
+        let mut snapshot = [0; CONFIG_REGISTERS.len()];
+        for (value, register) in snapshot.iter_mut().zip(CONFIG_REGISTERS) {
+            *value = self.read_register(register)?;
+        }
+        Ok(GoldenSnapshot(snapshot))
+    }
+
+    /// Reads the configuration registers and reconstructs the device
+    /// configuration, e.g. to compare it with the intended one.
+    ///
+    /// This is the inverse of [`Self::set_device_configuration`]. Returns
+    /// [`Error::InvalidEncoding`] if a register holds a value that isn't
+    /// representable in a [`DeviceConfig`].
+    pub fn read_config(&mut self) -> Result<DeviceConfig, Error<E>> {
+        let snapshot = self.capture_golden()?;
+        DeviceConfig::decode(|register| match register.config_index() {
+            Some(index) => snapshot.as_bytes()[index],
+            None => unreachable!("only configuration registers are decoded"),
+        })
+        .map_err(Error::InvalidEncoding)
+    }
+
+    /// Checks if the configuration registers still match the given snapshot.
+    pub fn check_against_golden(&mut self, golden: &GoldenSnapshot) -> Result<bool, Error<E>> {
+        Ok(self.capture_golden()? == *golden)
+    }
+
+    /// Writes a value to a given register.
+    ///
+    /// Writing to a register that requires configuration mode from a driver
+    /// in [`Normal`] mode returns [`Error::NotReadyToWrite`]. Writes to RESET
+    /// are always allowed, but they bypass the mode tracked by the type of the
+    /// driver, see [`Ldc3114::config_mode`] and [`Ldc3114::normal_mode`] to
+    /// switch modes.
+    pub fn write_register(&mut self, register: Register, value: u8) -> Result<(), Error<E>> {
+        if register.is_read_only() {
+            return Err(Error::WriteToReadOnly);
+        }
+
+        if register.requires_config_mode() && !MODE::CONFIG {
+            return Err(Error::NotReadyToWrite);
+        }
+
+        self.i2c
+            .write(I2C_ADDR, &[register.addr(), value])
+            .map_err(Error::I2c)
+    }
+
+    /// Reads a value from a given register.
+    pub fn read_register(&mut self, register: Register) -> Result<u8, Error<E>> {
+        let mut buffer = [0u8; 1];
+        self.i2c
+            .write_read(I2C_ADDR, &[register.addr()], &mut buffer)
+            .map_err(Error::I2c)?;
+
+        if let Register::Status = register {
+            self.latch_status(buffer[0]);
+        }
+        Ok(buffer[0])
+    }
+
+    /// Reads a value from the given kind of register of the given channel.
+    pub fn read_channel_register<T: ChannelRegisters>(
+        &mut self,
+        ch: T,
+        which: ChannelRegister,
+    ) -> Result<u8, Error<E>> {
+        self.read_register(ch.register(which))
+    }
+
+    /// Modifies the value of a given register.
+    pub fn modify_register<F>(&mut self, register: Register, f: F) -> Result<(), Error<E>>
+    where
+        F: FnOnce(u8) -> u8,
+    {
+        let value = self.read_register(register)?;
+        self.write_register(register, f(value))
+    }
+
+    /// Sets a field of a given register, leaving the other bits untouched.
+    ///
+    /// Returns [`Error::InvalidParameter`] without touching the bus if `value`
+    /// doesn't fit in the field.
+    fn modify_field(
+        &mut self,
+        register: Register,
+        field: BitField,
+        value: u8,
+    ) -> Result<(), Error<E>> {
+        if !field.fits(value) {
+            return Err(Error::InvalidParameter);
+        }
+        let current = self.read_register(register)?;
+        let new = field.set(current, value).ok_or(Error::InvalidParameter)?;
+        self.write_register(register, new)
+    }
+
+    /// Sets some bits of a given register.
+    pub fn set_register_bits(&mut self, register: Register, bits: u8) -> Result<(), Error<E>> {
+        self.modify_register(register, |v| v | bits)
+    }
+
+    /// Clears some bits of a given register.
+    pub fn clear_register_bits(&mut self, register: Register, bits: u8) -> Result<(), Error<E>> {
+        self.modify_register(register, |v| v & !bits)
+    }
+}
+
+impl<I2C, E, RST> Ldc3114<I2C, Normal, RST>
+where
+    I2C: embedded_hal::i2c::I2c + embedded_hal::i2c::ErrorType<Error = E>,
+{
+    /// Resets the device and register configurations.
+    ///
+    /// All registers will be returned to default values, which the cached
+    /// sensor configuration is reset to as well.
+    /// Normal operation will not resume until STATUS:CHIP_READY=1, see
+    /// [`Self::reset`] to wait for it.
+    pub fn full_reset(&mut self) -> Result<(), Error<E>> {
+        self.write_register(Register::Reset, FULL_RESET)?;
+        self.reset_state();
+        Ok(())
+    }
+
+    /// Resets the device like [`Self::full_reset`] and waits until the chip
+    /// is ready.
+    ///
+    /// Returns [`Error::Timeout`] if STATUS:CHIP_READY doesn't assert within
+    /// the polling budget, which tells a stuck device apart from bus errors.
+    pub fn reset<D: embedded_hal::delay::DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        self.full_reset()?;
+        self.wait_for_chip_ready(delay)
+    }
+
+    /// Enters configuration mode without waiting for the registers to become
+    /// ready to be written.
+    ///
+    /// Any device configuration changes should be made in this mode, and the
+    /// setters of configuration registers are only available on the returned
+    /// driver. See [`Self::enter_config_mode`] to also wait for
+    /// STATUS:RDY_TO_WRITE. On failure, the driver is handed back in normal
+    /// mode along with the error.
+    pub fn config_mode(mut self) -> Result<Ldc3114<I2C, Config, RST>, TransitionError<Self, E>> {
+        match self.write_register(Register::Reset, CONFIG_MODE) {
+            Ok(()) => Ok(self.into_mode()),
+            Err(error) => Err(TransitionError { ldc: self, error }),
+        }
+    }
+
+    /// Enters configuration mode and confirms that the registers became
+    /// ready to be written.
+    ///
+    /// Returns [`Error::Timeout`] if STATUS:RDY_TO_WRITE doesn't assert
+    /// within the polling budget, e.g. because the device was busy. The
+    /// device is then returned to normal mode on a best-effort basis and the
+    /// driver is handed back in normal mode along with the error.
+    pub fn enter_config_mode<D: embedded_hal::delay::DelayNs>(
+        self,
+        delay: &mut D,
+    ) -> Result<Ldc3114<I2C, Config, RST>, TransitionError<Self, E>> {
+        let mut ldc = self.config_mode()?;
+        match ldc.wait_for_ready_to_write(delay) {
+            Ok(()) => Ok(ldc),
+            Err(error) => {
+                // The wait error is reported, even if leaving configuration
+                // mode fails as well.
+                let _ = ldc.leave_config_mode();
+                Err(TransitionError {
+                    ldc: ldc.into_mode(),
+                    error,
+                })
+            }
+        }
+    }
+
+    /// Runs `f` in configuration mode.
+    ///
+    /// The device enters configuration mode and `f` runs once registers are
+    /// ready to be written, with a driver in [`Config`] mode borrowing the bus.
+    /// Normal mode is restored afterwards, even if `f` or the wait returned an
+    /// error, in which case that error is returned. Changes to the cached
+    /// state made through the borrowed driver are kept.
+    pub fn with_config_session<R, D: embedded_hal::delay::DelayNs>(
+        &mut self,
+        delay: &mut D,
+        f: impl FnOnce(&mut Ldc3114<&mut I2C, Config>) -> Result<R, Error<E>>,
+    ) -> Result<R, Error<E>> {
+        self.write_register(Register::Reset, CONFIG_MODE)?;
+        let mut ldc = Ldc3114 {
+            i2c: &mut self.i2c,
+            reset: NoReset,
+            mode: PhantomData::<Config>,
+            state: self.state,
+        };
+        let result = match ldc.wait_for_ready_to_write(delay) {
+            Ok(()) => f(&mut ldc),
+            Err(e) => Err(e),
+        };
+        let exit = ldc.leave_config_mode();
+        self.state = ldc.state;
+        let value = result?;
+        exit?;
+        Ok(value)
+    }
+
+    /// Writes the entire device configuration, entering configuration mode
+    /// for the duration of the writes.
+    ///
+    /// The configuration is validated before anything is written, see
+    /// [`DeviceConfig::validate`]. Shared registers are composed from the
+    /// settings of all channels. Once
+    /// back in normal mode, the cached sensor configuration is refreshed from
+    /// the device. Unlike [`Self::configure_and_verify`], the configuration
+    /// isn't read back.
+    pub fn apply_config<D: embedded_hal::delay::DelayNs>(
+        &mut self,
+        config: &DeviceConfig,
+        delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        config.validate().map_err(Error::InvalidConfig)?;
+        self.with_config_session(delay, |ldc| ldc.set_device_configuration(config))
+    }
+
+    /// Sets up the entire device configuration and verifies it.
+    ///
+    /// The configuration is written with [`Self::apply_config`], so normal
+    /// mode is restored even if a write fails. Once the chip is ready, the
+    /// configuration is read back and [`Error::Verification`] is returned if
+    /// any field defined by `config` doesn't match. Reserved bits are ignored.
+    pub fn configure_and_verify<D: embedded_hal::delay::DelayNs>(
+        &mut self,
+        config: &DeviceConfig,
+        delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        self.apply_config(config, delay)?;
+        self.wait_for_chip_ready(delay)?;
+
+        for (register, value, mask) in config.encode() {
+            let actual = self.read_register(register)?;
+            if actual & mask != value {
+                return Err(Error::Verification);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Brings up a unit on a production test fixture.
+    ///
+    /// The device is verified with [`Self::verify_device`], configured and
+    /// verified with [`Self::configure_and_verify`], and a snapshot of the
+    /// status and all channels is captured in a single transaction. The
+    /// reported status accumulates every status read made along the way,
+    /// including the readiness polls, so a fault flag cleared by an earlier
+    /// read isn't missed. Flags accumulated before the call are discarded. A
+    /// configuration mismatch or an error flag is reported as a failing
+    /// [`BringupReport`], while other errors are returned as is.
+    pub fn production_bringup<D: embedded_hal::delay::DelayNs>(
+        &mut self,
+        config: &DeviceConfig,
+        delay: &mut D,
+    ) -> Result<BringupReport, Error<E>> {
+        self.take_status();
+        self.verify_device()?;
+        let config_verified = match self.configure_and_verify(config, delay) {
+            Ok(()) => true,
+            Err(Error::Verification) => false,
+            Err(e) => return Err(e),
+        };
+
+        let mut buffer = [0; 10];
+        let channels = self.measure_all_with_buffer(&mut buffer)?;
+        Ok(BringupReport {
+            config_verified,
+            status: self.take_status(),
+            channels,
+        })
+    }
+
+    /// Temporarily sets the scan rate in normal power mode while running `f`.
+    ///
+    /// The cached scan rate returned by [`Self::scan_rate`] is saved and
+    /// restored afterwards with [`Self::set_scan_rate`], so only the scan rate
+    /// field of NP_SCAN_RATE is written. The scan rate can only be written in
+    /// configuration mode, so the device briefly enters it around each change,
+    /// which is why this is only available in normal mode. `delay` is used to
+    /// poll STATUS:RDY_TO_WRITE after each entry, as the device may not accept
+    /// writes right away.
+    ///
+    /// Returns [`Error::CacheInvalidated`] without touching the device if the
+    /// cached scan rate is unknown.
+    pub fn with_scan_rate<R, D: embedded_hal::delay::DelayNs>(
+        &mut self,
+        sr: ScanRate,
+        delay: &mut D,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> Result<R, Error<E>> {
+        let previous = self.scan_rate().ok_or(Error::CacheInvalidated)?;
+        self.with_config_session(delay, |ldc| ldc.set_scan_rate(sr))?;
+        let result = f(self);
+        self.with_config_session(delay, |ldc| ldc.set_scan_rate(previous))?;
+        Ok(result)
+    }
+
+    /// Advances `scanner` by one tick and enables only the channels that are
+    /// due in normal mode.
+    ///
+    /// Channels that aren't due are disabled in both normal and low power
+    /// mode, so their LPEN bits are cleared, while due channels keep theirs.
+    /// Save the enable state with [`Self::save_enable_state`] before
+    /// scheduling to restore it afterwards. Returns the enabled channels, whose
+    /// data can be read once new data is available. Like
+    /// [`Self::with_scan_rate`], this briefly enters configuration mode and
+    /// is only available in normal mode. See [`ScheduledScanner`] for the
+    /// limitations of this approach.
+    pub fn scan_scheduled<D: embedded_hal::delay::DelayNs>(
+        &mut self,
+        scanner: &mut ScheduledScanner,
+        delay: &mut D,
+    ) -> Result<ChannelSet, Error<E>> {
+        let due = scanner.tick();
+        let en = self.read_register(Register::En)?;
+        self.write_in_config_mode(Register::En, (en & (due.bits() << 4)) | due.bits(), delay)?;
+        Ok(due)
+    }
+
+    /// Temporarily disables the button algorithm while running `f`, e.g. to
+    /// capture raw measurements.
+    ///
+    /// The previous state of INTPOL:BTN_ALG_EN is restored afterwards. Like
+    /// [`Self::with_scan_rate`], this briefly enters configuration mode around
+    /// each change and is only available in normal mode.
+    pub fn with_button_algorithm_disabled<R, D: embedded_hal::delay::DelayNs>(
+        &mut self,
+        delay: &mut D,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> Result<R, Error<E>> {
+        let intpol = self.read_register(Register::IntPol)?;
+        self.write_in_config_mode(Register::IntPol, intpol & !BTN_ALG_EN, delay)?;
+        let result = f(self);
+        let current = self.read_register(Register::IntPol)?;
+        let restored = (current & !BTN_ALG_EN) | (intpol & BTN_ALG_EN);
+        self.write_in_config_mode(Register::IntPol, restored, delay)?;
+        Ok(result)
+    }
+
+    /// Writes a register from normal mode by briefly entering configuration mode.
+    fn write_in_config_mode<D: embedded_hal::delay::DelayNs>(
+        &mut self,
+        register: Register,
+        value: u8,
+        delay: &mut D,
+    ) -> Result<(), Error<E>> {
+        self.with_config_session(delay, |ldc| ldc.write_register(register, value))
+    }
+}
+
+impl<I2C, E, RST> Ldc3114<I2C, Config, RST>
+where
+    I2C: embedded_hal::i2c::I2c + embedded_hal::i2c::ErrorType<Error = E>,
+{
+    /// Exits configuration mode entered with [`Ldc3114::enter_config_mode`].
+    ///
+    /// This is the same as [`Self::normal_mode`], so several setters can be
+    /// batched between a single enter/exit pair.
+    pub fn exit_config_mode(self) -> Result<Ldc3114<I2C, Normal, RST>, TransitionError<Self, E>> {
+        self.normal_mode()
+    }
+
+    /// Enters normal mode (exits configuration mode).
+    ///
+    /// The cached sensor configuration is refreshed from the device first, as
+    /// it may have been changed. On failure, the driver is handed back in
+    /// configuration mode along with the error.
+    pub fn normal_mode(mut self) -> Result<Ldc3114<I2C, Normal, RST>, TransitionError<Self, E>> {
+        match self.leave_config_mode() {
+            Ok(()) => Ok(self.into_mode()),
+            Err(error) => Err(TransitionError { ldc: self, error }),
+        }
+    }
+
+    /// Refreshes the cache and returns the device to normal mode, leaving
+    /// the type of the driver to the caller.
+    fn leave_config_mode(&mut self) -> Result<(), Error<E>> {
+        self.refresh_cache()?;
+        self.write_register(Register::Reset, 0)
+    }
+
+    /// Sets up the entire device configuration.
+    ///
+    /// Returns [`Error::InvalidConfig`] without writing anything if the
+    /// configuration doesn't pass [`DeviceConfig::validate`].
+    pub fn set_device_configuration(&mut self, config: &DeviceConfig) -> Result<(), Error<E>> {
+        config.validate().map_err(Error::InvalidConfig)?;
+
+        for (register, value, mask) in config.encode() {
+            if mask == 0xFF {
+                self.write_register(register, value)?;
+            } else {
+                self.modify_register(register, |v| (v & !mask) | value)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Configures a given channel.
+    pub fn configure_channel<T: ChannelRegisters>(
+        &mut self,
+        ch: T,
+        config: &ChannelConfig,
+    ) -> Result<(), Error<E>> {
+        self.set_channel_mode(ch, config.mode)?;
+        self.set_channel_gain(ch, config.gain)?;
+        self.set_output_polarity(ch, config.output_polarity)?;
+        self.set_counter_scale(ch, config.counter_scale)?;
         self.set_fast_tracking_factor(ch, config.fast_tracking_factor)?;
         self.set_data_polarity(ch, config.data_polarity)?;
         self.set_sensor_config(ch, &config.sensor_config)?;
@@ -352,24 +1248,121 @@ where
         }
     }
 
+    /// Restores the EN register value returned by
+    /// [`save_enable_state`](Self::save_enable_state).
+    ///
+    /// Like any write to EN, this requires configuration mode.
+    pub fn restore_enable_state(&mut self, saved: u8) -> Result<(), Error<E>> {
+        self.write_register(Register::En, saved)
+    }
+
+    /// Disables all channels in both normal and low power mode.
+    ///
+    /// This is useful to pause sensing while an adjacent subsystem injects
+    /// interference. Save the enable state first to restore it afterwards.
+    /// Like any write to EN, this requires configuration mode.
+    pub fn disable_all_channels(&mut self) -> Result<(), Error<E>> {
+        self.write_register(Register::En, 0x00)
+    }
+
+    /// Sets all channels to the given operating mode.
+    ///
+    /// Like any write to EN, this requires configuration mode.
+    pub fn enable_all_channels(&mut self, mode: ChannelMode) -> Result<(), Error<E>> {
+        let en = Channel0::EN_BIT | Channel1::EN_BIT | Channel2::EN_BIT | Channel3::EN_BIT;
+        let lpen =
+            Channel0::LPEN_BIT | Channel1::LPEN_BIT | Channel2::LPEN_BIT | Channel3::LPEN_BIT;
+        let value = match mode {
+            ChannelMode::Disabled => 0x00,
+            ChannelMode::NormalMode => en,
+            ChannelMode::NormalAndLowPowerMode => en | lpen,
+        };
+        self.write_register(Register::En, value)
+    }
+
+    /// Disables the given channel and puts its sensor configuration in a
+    /// benign state.
+    ///
+    /// Besides clearing the channel's EN and LPEN bits, the gain is set to 0
+    /// and the sensor is configured with a cycle count of 0 and the lowest
+    /// Rp and frequency ranges.
+    pub fn disable_channel_fully<T: ChannelRegisters>(&mut self, ch: T) -> Result<(), Error<E>> {
+        const DISABLED_SENSOR_CONFIG: SensorConfig = SensorConfig {
+            rp_range: RpRange::Rp50OhmTo4kOhm,
+            frequency_range: FrequencyRange::Freq1MHzTo3_3MHz,
+            cycle_count: 0,
+        };
+
+        self.set_channel_mode(ch, ChannelMode::Disabled)?;
+        self.set_channel_gain(ch, 0)?;
+        self.set_sensor_config(ch, &DISABLED_SENSOR_CONFIG)
+    }
+
     /// Sets the gain for the given channel.
+    ///
+    /// This is the same as [`Self::set_gain`].
     pub fn set_channel_gain<T: ChannelRegisters>(
         &mut self,
         ch: T,
         gain: u8,
     ) -> Result<(), Error<E>> {
-        if gain >= 0x40 {
-            return Err(Error::InvalidParameter);
-        }
-        self.write_register(ch.gain(), gain)
+        self.set_gain(ch, gain)
+    }
+
+    /// Sets the gain for the given channel.
+    ///
+    /// The gain is 6 bits wide, so values above 0x3F are rejected with
+    /// [`Error::InvalidParameter`] instead of being truncated. The reserved
+    /// upper bits of the register are preserved.
+    pub fn set_gain<T: ChannelRegisters>(&mut self, ch: T, gain: u8) -> Result<(), Error<E>> {
+        self.modify_field(ch.gain(), GAIN, gain)
     }
 
     /// Sets the scan rate in normal power mode.
+    ///
+    /// This is the same as [`Self::set_scan_rate`].
     pub fn set_normal_scan_rate(&mut self, sr: ScanRate) -> Result<(), Error<E>> {
-        self.write_register(Register::NpScanRate, sr as u8)
+        self.set_scan_rate(sr)
+    }
+
+    /// Sets the scan rate in normal power mode.
+    ///
+    /// Only the scan rate field in the lower bits of NP_SCAN_RATE is
+    /// modified, the reserved upper bits are preserved.
+    ///
+    /// The cached scan rate returned by [`Self::scan_rate`] is updated along
+    /// with the register.
+    pub fn set_scan_rate(&mut self, sr: ScanRate) -> Result<(), Error<E>> {
+        self.modify_field(Register::NpScanRate, NP_SCAN_RATE, sr as u8)?;
+        self.state.scan_rate = sr as u8;
+        Ok(())
+    }
+
+    /// Sets the scan rate in normal power mode and returns the resulting
+    /// samples per second of each enabled channel.
+    ///
+    /// The cached scan rate is updated like with [`Self::set_scan_rate`].
+    /// The scan rate sets the interval between scans, not between
+    /// conversions, and every scan converts all enabled channels in
+    /// sequence. The enabled-channel count therefore doesn't divide the
+    /// rate, and each enabled channel is sampled at the nominal scan rate.
+    /// Returns 0 if no channel is enabled and
+    /// `u16::MAX` for [`ScanRate::Continuous`], whose rate depends on the
+    /// conversion time of the enabled channels.
+    pub fn set_scan_rate_reporting(&mut self, sr: ScanRate) -> Result<u16, Error<E>> {
+        self.set_normal_scan_rate(sr)?;
+        let en = self.read_register(Register::En)?;
+        if en & (EN0 | EN1 | EN2 | EN3) == 0 {
+            return Ok(0);
+        }
+        Ok(sr.sps())
     }
 
     /// Sets the scan rate in low power mode.
+    ///
+    /// Like all configuration registers, LP_SCAN_RATE can only be written in
+    /// configuration mode once STATUS:RDY_TO_WRITE is set, see
+    /// [`Ldc3114::enter_config_mode`].
     pub fn set_low_power_scan_rate(&mut self, sr: LowPowerScanRate) -> Result<(), Error<E>> {
         self.write_register(Register::LpScanRate, sr as u8)
     }
@@ -401,6 +1394,20 @@ where
         }
     }
 
+    /// Selects the event that drives pin INTB.
+    ///
+    /// See [`InterruptSource`] for the effect on the button algorithm.
+    /// [`InterruptSource::Both`] isn't supported by the device and returns
+    /// [`Error::InvalidParameter`] without touching the bus.
+    pub fn set_interrupt_source(&mut self, source: InterruptSource) -> Result<(), Error<E>> {
+        let enable = match source {
+            InterruptSource::DataReady => false,
+            InterruptSource::ButtonChange => true,
+            InterruptSource::Both => return Err(Error::InvalidParameter),
+        };
+        self.enable_button_press_detection_algorithm(enable)
+    }
+
     /// Enables/disables the button press detection algorithm to assert events on OUT_X pins.
     pub fn enable_button_press_detection_algorithm(
         &mut self,
@@ -426,19 +1433,37 @@ where
     }
 
     /// Sets the baseline tracking increment in normal power mode.
+    ///
+    /// This is the same as [`Self::set_baseline_increment_np`].
     pub fn set_baseline_tracking_increment_np(&mut self, value: u8) -> Result<(), Error<E>> {
-        if value >= 0x08 {
-            return Err(Error::InvalidParameter);
-        }
-        self.write_register(Register::NpBaseInc, value)
+        self.set_baseline_increment_np(value)
+    }
+
+    /// Sets the baseline tracking increment in normal power mode.
+    ///
+    /// The increment is held in bits 2:0 of NP_BASE_INC, so the valid range
+    /// is `0..=0x07`. Other values are rejected with
+    /// [`Error::InvalidParameter`], and the other bits of the register are
+    /// preserved.
+    pub fn set_baseline_increment_np(&mut self, value: u8) -> Result<(), Error<E>> {
+        self.modify_field(Register::NpBaseInc, BASE_INC, value)
     }
 
     /// Sets the baseline tracking increment in low power mode.
+    ///
+    /// This is the same as [`Self::set_baseline_increment_lp`].
     pub fn set_baseline_tracking_increment_lp(&mut self, value: u8) -> Result<(), Error<E>> {
-        if value >= 0x08 {
-            return Err(Error::InvalidParameter);
-        }
-        self.write_register(Register::LpBaseInc, value)
+        self.set_baseline_increment_lp(value)
+    }
+
+    /// Sets the baseline tracking increment in low power mode.
+    ///
+    /// The increment is held in bits 2:0 of LP_BASE_INC, so the valid range
+    /// is `0..=0x07`. Other values are rejected with
+    /// [`Error::InvalidParameter`], and the other bits of the register are
+    /// preserved.
+    pub fn set_baseline_increment_lp(&mut self, value: u8) -> Result<(), Error<E>> {
+        self.modify_field(Register::LpBaseInc, BASE_INC, value)
     }
 
     /// Configures baseline tracking to pause or not for the given channel
@@ -457,70 +1482,153 @@ where
 
     /// Configures whether to include or exclude the given channel
     /// from the Max-Win Button algorithm.
+    ///
+    /// Equivalent to [`Self::set_max_win`].
     pub fn include_channel_in_max_win_algorithm<T: ChannelRegisters>(
         &mut self,
-        _ch: T,
+        ch: T,
         include: bool,
     ) -> Result<(), Error<E>> {
-        if include {
+        self.set_max_win(ch, include)
+    }
+
+    /// Includes or excludes the given channel from the Max-Win Button
+    /// algorithm.
+    ///
+    /// Only the MAXWIN bit of the given channel is changed, the BTPAUSE bits
+    /// and the bits of the other channels are preserved.
+    pub fn set_max_win<T: ChannelRegisters>(
+        &mut self,
+        _ch: T,
+        enable: bool,
+    ) -> Result<(), Error<E>> {
+        if enable {
             self.set_register_bits(Register::BtPauseMaxWin, T::MAXWIN_BIT)
         } else {
             self.clear_register_bits(Register::BtPauseMaxWin, T::MAXWIN_BIT)
         }
     }
 
+    /// Sets which channels are included in the Max-Win Button algorithm.
+    ///
+    /// Channels not in `channels` are excluded from the algorithm.
+    pub fn set_max_win_group(&mut self, channels: impl Into<ChannelSet>) -> Result<(), Error<E>> {
+        let bits = channels.into().bits();
+        self.modify_field(Register::BtPauseMaxWin, MAXWIN_GROUP, bits)
+    }
+
     /// Sets the LC oscillation frequency divider.
+    ///
+    /// The divider field is 3 bits wide, so the valid range is `0..=0x07`.
+    /// Other values are rejected with [`Error::InvalidParameter`], and the
+    /// reserved upper bits of the register are preserved. The cached divider
+    /// used by [`Self::read_sensor_frequency`] is updated along with the
+    /// register.
     pub fn set_lc_divider(&mut self, value: u8) -> Result<(), Error<E>> {
-        if value >= 0x08 {
-            return Err(Error::InvalidParameter);
-        }
-        self.write_register(Register::LcDivider, value)
+        self.modify_field(Register::LcDivider, LC_DIVIDER, value)?;
+        self.state.lcdiv = value;
+        Ok(())
     }
 
-    /// Hysteresis for threshold for button algorithm.
+    /// Sets the hysteresis for the threshold of the button algorithm.
+    ///
+    /// The hysteresis field is 4 bits wide, so the valid range is
+    /// `0..=0x0F`. Other values are rejected with
+    /// [`Error::InvalidParameter`]. The reserved upper bits of the register
+    /// are preserved.
     pub fn set_hysteresis(&mut self, value: u8) -> Result<(), Error<E>> {
-        if value >= 0x10 {
-            return Err(Error::InvalidParameter);
-        }
-        self.write_register(Register::Hyst, value)
+        self.modify_field(Register::Hyst, HYST, value)
     }
 
     /// Sets the anti-twist threshold value for the anti-twist button algorithm.
+    ///
+    /// The anti-twist field is 3 bits wide, so the valid range is `0..=0x07`.
+    /// Other values are rejected with [`Error::InvalidParameter`], and the
+    /// reserved upper bits of the register are preserved.
     pub fn set_antitwist(&mut self, value: u8) -> Result<(), Error<E>> {
-        if value >= 0x08 {
-            return Err(Error::InvalidParameter);
-        }
-        self.write_register(Register::Twist, value)
+        self.modify_field(Register::Twist, TWIST, value)
     }
 
     /// Configures whether to include or exclude the given channel
     /// from the Anti-Common Button algorithm.
+    ///
+    /// Equivalent to [`Self::set_anticommon`].
     pub fn include_channel_in_anticommon_algorithm<T: ChannelRegisters>(
         &mut self,
-        _ch: T,
+        ch: T,
         include: bool,
     ) -> Result<(), Error<E>> {
-        if include {
-            self.set_register_bits(Register::CommonDeform, T::ANTICOM_BIT)
-        } else {
-            self.clear_register_bits(Register::CommonDeform, T::ANTICOM_BIT)
-        }
+        self.set_anticommon(ch, include)
     }
 
-    /// Configures whether to include or exclude the given channel
-    /// from the Anti-Deform Button algorithm.
-    pub fn include_channel_in_antideform_algorithm<T: ChannelRegisters>(
+    /// Includes or excludes the given channel from the Anti-Common Button
+    /// algorithm.
+    ///
+    /// Only the ANTICOM bit of the given channel is changed, the ANTIDFORM bits
+    /// and the bits of the other channels are preserved.
+    pub fn set_anticommon<T: ChannelRegisters>(
         &mut self,
         _ch: T,
+        enable: bool,
+    ) -> Result<(), Error<E>> {
+        if enable {
+            self.set_register_bits(Register::CommonDeform, T::ANTICOM_BIT)
+        } else {
+            self.clear_register_bits(Register::CommonDeform, T::ANTICOM_BIT)
+        }
+    }
+
+    /// Configures whether to include or exclude the given channel
+    /// from the Anti-Deform Button algorithm.
+    ///
+    /// Equivalent to [`Self::set_antideform`].
+    pub fn include_channel_in_antideform_algorithm<T: ChannelRegisters>(
+        &mut self,
+        ch: T,
         include: bool,
     ) -> Result<(), Error<E>> {
-        if include {
+        self.set_antideform(ch, include)
+    }
+
+    /// Includes or excludes the given channel from the Anti-Deform Button
+    /// algorithm.
+    ///
+    /// Only the ANTIDFORM bit of the given channel is changed, the ANTICOM bits
+    /// and the bits of the other channels are preserved.
+    pub fn set_antideform<T: ChannelRegisters>(
+        &mut self,
+        _ch: T,
+        enable: bool,
+    ) -> Result<(), Error<E>> {
+        if enable {
             self.set_register_bits(Register::CommonDeform, T::ANTIDFORM_BIT)
         } else {
             self.clear_register_bits(Register::CommonDeform, T::ANTIDFORM_BIT)
         }
     }
 
+    /// Sets which channels are included in the Anti-Common Button algorithm.
+    ///
+    /// Channels not in `channels` are excluded from the algorithm.
+    pub fn set_anticommon_group(
+        &mut self,
+        channels: impl Into<ChannelSet>,
+    ) -> Result<(), Error<E>> {
+        let bits = channels.into().bits();
+        self.modify_field(Register::CommonDeform, ANTICOM_GROUP, bits)
+    }
+
+    /// Sets which channels are included in the Anti-Deform Button algorithm.
+    ///
+    /// Channels not in `channels` are excluded from the algorithm.
+    pub fn set_antideform_group(
+        &mut self,
+        channels: impl Into<ChannelSet>,
+    ) -> Result<(), Error<E>> {
+        let bits = channels.into().bits();
+        self.modify_field(Register::CommonDeform, ANTIDFORM_GROUP, bits)
+    }
+
     /// Sets the output polarity of the given channel.
     pub fn set_output_polarity<T: ChannelRegisters>(
         &mut self,
@@ -546,15 +1654,15 @@ where
     }
 
     /// Sets the counter scale for the given channel.
+    ///
+    /// All channels share the CNTSC register, so it is read, modified and
+    /// written back with only the two bits of the given channel changed.
     pub fn set_counter_scale<T: ChannelRegisters>(
         &mut self,
         _ch: T,
         scale: CounterScale,
     ) -> Result<(), Error<E>> {
-        self.modify_register(Register::Cntsc, |mut v| {
-            v &= !T::CNTSC_MASK;
-            v | (scale as u8) << T::CNTSC_OFFSET
-        })
+        self.modify_field(Register::Cntsc, cntsc_field::<T>(), scale as u8)
     }
 
     /// Sets the sensor configuration for the given channel.
@@ -563,66 +1671,1322 @@ where
         ch: T,
         config: &SensorConfig,
     ) -> Result<(), Error<E>> {
-        if config.cycle_count >= 0x20 {
-            return Err(Error::InvalidParameter);
-        }
-
-        let mut value = config.cycle_count;
+        let mut value = SENCY
+            .set(0, config.cycle_count)
+            .ok_or(Error::InvalidParameter)?;
         value |= config.rp_range as u8;
         value |= config.frequency_range as u8;
 
         self.write_register(ch.sensor_config(), value)
     }
 
+    /// Sets the sensor Rp range for the given channel,
+    /// leaving the rest of its sensor configuration untouched.
+    pub fn set_rp_range<T: ChannelRegisters>(
+        &mut self,
+        ch: T,
+        range: RpRange,
+    ) -> Result<(), Error<E>> {
+        self.modify_field(ch.sensor_config(), RP_RANGE, RP_RANGE.get(range as u8))
+    }
+
+    /// Sets the sensor frequency range for the given channel,
+    /// leaving the rest of its sensor configuration untouched.
+    pub fn set_frequency_range<T: ChannelRegisters>(
+        &mut self,
+        ch: T,
+        range: FrequencyRange,
+    ) -> Result<(), Error<E>> {
+        self.modify_field(ch.sensor_config(), FREQ, FREQ.get(range as u8))
+    }
+
     /// Sets the Fast Tracking Factor (FTF) for the given channel.
+    ///
+    /// Channels 1 and 2 share the FTF1_2 register, so it is read, modified and
+    /// written back with only the bits of the given channel changed. The same
+    /// applies to the other bits of FTF0 and FTF3.
     pub fn set_fast_tracking_factor<T: ChannelRegisters>(
         &mut self,
         ch: T,
         ftf: FastTrackingFactor,
     ) -> Result<(), Error<E>> {
-        self.modify_register(ch.ftf(), |mut v| {
-            v &= !T::FTF_MASK;
-            v | (ftf as u8) << T::FTF_OFFSET
-        })
+        self.modify_field(ch.ftf(), ftf_field::<T>(), ftf as u8)
     }
 
-    /// Writes a value to a given register.
-    pub fn write_register(&mut self, register: Register, value: u8) -> Result<(), Error<E>> {
-        if register.is_read_only() {
-            return Err(Error::WriteToReadOnly);
+    /// Sets the Fast Tracking Factor (FTF) of all channels, indexed by
+    /// channel number.
+    ///
+    /// FTF1_2 is composed from channels 1 and 2 and written at once, so only
+    /// three registers are written. The bits outside the FTF fields of all
+    /// three registers, including the reserved low nibble of FTF1_2, are
+    /// preserved.
+    pub fn set_all_ftf(&mut self, ftf: [FastTrackingFactor; 4]) -> Result<(), Error<E>> {
+        let ftf1_2 = ((ftf[1] as u8) << FTF1_OFFSET) | ((ftf[2] as u8) << FTF2_OFFSET);
+        let ftf0 = ftf_field::<Channel0>();
+        let ftf3 = ftf_field::<Channel3>();
+        self.modify_field(Register::Ftf0, ftf0, ftf[0] as u8)?;
+        self.modify_register(Register::Ftf1_2, |value| {
+            (value & !(FTF1_MASK | FTF2_MASK)) | ftf1_2
+        })?;
+        self.modify_field(Register::Ftf3, ftf3, ftf[3] as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::boxed::Box;
+
+    use crate::mock::{Call, CallLog, MockDelay, MockI2c, MockPin, NoDelay};
+    use crate::register::{DATA_RDY, OUT_STATUS, OUT0, OUT3};
+    use crate::*;
+    use embedded_hal::i2c::ErrorKind;
+
+    #[test]
+    fn apply_config_times_out_when_never_ready_to_write() {
+        let mut i2c = MockI2c::new();
+        i2c.ready_to_write = false;
+        let mut ldc = Ldc3114::new(i2c);
+
+        let result = ldc.apply_config(&DeviceConfig::const_default(), &mut NoDelay);
+
+        assert!(matches!(result, Err(Error::Timeout)));
+        // Only configuration mode was entered and left again.
+        let reset = Register::Reset.addr();
+        assert_eq!(ldc.i2c.writes, [(reset, CONFIG_MODE), (reset, 0)]);
+    }
+
+    #[test]
+    fn enter_config_mode_times_out_back_in_normal_mode() {
+        let mut i2c = MockI2c::new();
+        i2c.ready_to_write = false;
+
+        let Err(error) = Ldc3114::new(i2c).enter_config_mode(&mut NoDelay) else {
+            panic!("entered configuration mode");
+        };
+
+        assert!(matches!(error.error, Error::Timeout));
+        assert_eq!(error.ldc.i2c.get(Register::Reset), 0);
+    }
+
+    /// A driver in configuration mode on the given bus.
+    fn config_driver(i2c: MockI2c) -> Ldc3114<MockI2c, Config> {
+        let Ok(ldc) = Ldc3114::new(i2c).config_mode() else {
+            panic!("failed to enter configuration mode");
+        };
+        ldc
+    }
+
+    /// Simulates a new conversion of channel 0, incrementing its processed
+    /// and raw data.
+    fn next_conversion(_start: u8, regs: &mut [u8; 256]) {
+        let data = Register::Data0Lsb as usize..=Register::Data0Msb as usize;
+        let value = u16::from_le_bytes([regs[*data.start()], regs[*data.end()]]);
+        regs[data].copy_from_slice(&(value.wrapping_add(1) & 0x0FFF).to_le_bytes());
+
+        let raw = Register::RawData0_3 as usize..=Register::RawData0_1 as usize;
+        let mut bytes = [0; 4];
+        bytes[1..].copy_from_slice(&regs[raw.clone()]);
+        let value = u32::from_be_bytes(bytes).wrapping_add(1);
+        regs[raw].copy_from_slice(&value.to_be_bytes()[1..]);
+    }
+
+    #[test]
+    fn read_data_is_not_torn_by_a_new_conversion() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::Data0Lsb, 0xFF);
+        i2c.set(Register::Data0Msb, 0x00);
+        i2c.after_read = Some(Box::new(next_conversion));
+        let mut ldc = Ldc3114::new(i2c);
+
+        // Separate byte reads straddle the conversion and tear the value.
+        let lsb = ldc.read_register(Register::Data0Lsb).unwrap();
+        let msb = ldc.read_register(Register::Data0Msb).unwrap();
+        assert_eq!(u16::from_le_bytes([lsb, msb]), 0x1FF);
+
+        // The conversions so far took the value to 0x101.
+        assert_eq!(ldc.read_data(Channel0).unwrap(), 0x101);
+        assert_eq!(ldc.read_data(Channel0).unwrap(), 0x102);
+    }
+
+    #[test]
+    fn read_raw_data_is_not_torn_by_a_new_conversion() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::RawData0_3, 0x00);
+        i2c.set(Register::RawData0_2, 0xFF);
+        i2c.set(Register::RawData0_1, 0xFF);
+        i2c.after_read = Some(Box::new(next_conversion));
+        let mut ldc = Ldc3114::new(i2c);
+
+        assert_eq!(ldc.read_raw_data(Channel0).unwrap(), 0x00_FFFF);
+        assert_eq!(ldc.read_raw_data(Channel0).unwrap(), 0x01_0000);
+    }
+
+    #[test]
+    fn verify_device_double_read_rejects_changing_ids() {
+        let mut i2c = MockI2c::new();
+        i2c.after_read = Some(Box::new(|_, regs| {
+            regs[Register::DeviceIdLsb as usize] ^= 0x01;
+        }));
+        let mut ldc = Ldc3114::new(i2c);
+
+        assert!(matches!(
+            ldc.verify_device_double_read(),
+            Err(Error::UnstableRead)
+        ));
+    }
+
+    #[test]
+    fn write_register_rejects_config_registers_in_normal_mode() {
+        let mut ldc = Ldc3114::new(MockI2c::new());
+
+        assert!(matches!(
+            ldc.write_register(Register::Gain0, 0x12),
+            Err(Error::NotReadyToWrite)
+        ));
+        assert!(ldc.i2c.writes.is_empty());
+
+        ldc.write_register(Register::Reset, 0).unwrap();
+        assert_eq!(ldc.i2c.writes, [(Register::Reset.addr(), 0)]);
+    }
+
+    #[test]
+    fn write_register_allows_config_registers_in_config_mode() {
+        let mut ldc = config_driver(MockI2c::new());
+
+        ldc.write_register(Register::Gain0, 0x12).unwrap();
+        assert_eq!(ldc.i2c.get(Register::Gain0), 0x12);
+    }
+
+    #[test]
+    fn read_raw_data_is_unsigned_and_msb_first() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::RawData0_3, 0xFF);
+        i2c.set(Register::RawData0_2, 0xFF);
+        i2c.set(Register::RawData0_1, 0xFF);
+        i2c.set(Register::RawData3_3, 0x12);
+        i2c.set(Register::RawData3_2, 0x34);
+        i2c.set(Register::RawData3_1, 0x56);
+        let mut ldc = Ldc3114::new(i2c);
+
+        assert_eq!(ldc.read_raw_data(Channel0).unwrap(), 0x00FF_FFFF);
+        assert_eq!(ldc.read_raw_data(Channel3).unwrap(), 0x0012_3456);
+    }
+
+    #[test]
+    fn set_fast_tracking_factor_preserves_other_channels() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::Ftf0, 0xF9);
+        i2c.set(Register::Ftf1_2, 0xC0);
+        let mut ldc = config_driver(i2c);
+
+        ldc.set_fast_tracking_factor(Channel0, FastTrackingFactor::Two)
+            .unwrap();
+        ldc.set_fast_tracking_factor(Channel1, FastTrackingFactor::One)
+            .unwrap();
+
+        assert_eq!(ldc.i2c.get(Register::Ftf0), 0xFD);
+        // Channel 2 keeps FTF 3 next to the new FTF of channel 1.
+        assert_eq!(ldc.i2c.get(Register::Ftf1_2), 0xD0);
+    }
+
+    #[test]
+    fn set_counter_scale_preserves_other_channels() {
+        let mut i2c = MockI2c::new();
+        // Counter scales 3, 2, 1 and 0 for channels 3 to 0.
+        i2c.set(Register::Cntsc, 0xE4);
+        let mut ldc = config_driver(i2c);
+
+        ldc.set_counter_scale(Channel1, CounterScale::Three)
+            .unwrap();
+        assert_eq!(ldc.i2c.get(Register::Cntsc), 0xEC);
+
+        ldc.set_counter_scale(Channel2, CounterScale::Zero).unwrap();
+        assert_eq!(ldc.i2c.get(Register::Cntsc), 0xCC);
+    }
+
+    #[test]
+    fn set_anticommon_preserves_antideform_and_other_channels() {
+        let mut i2c = MockI2c::new();
+        // Channels 1 and 3 in Anti-Common, 0 and 2 in Anti-Deform.
+        i2c.set(Register::CommonDeform, 0xA5);
+        let mut ldc = config_driver(i2c);
+
+        ldc.set_anticommon(Channel0, true).unwrap();
+        assert_eq!(ldc.i2c.get(Register::CommonDeform), 0xB5);
+
+        ldc.set_anticommon(Channel3, false).unwrap();
+        assert_eq!(ldc.i2c.get(Register::CommonDeform), 0x35);
+    }
+
+    #[test]
+    fn set_antideform_preserves_anticommon_and_other_channels() {
+        let mut i2c = MockI2c::new();
+        // Channels 1 and 3 in Anti-Common, 0 and 2 in Anti-Deform.
+        i2c.set(Register::CommonDeform, 0xA5);
+        let mut ldc = config_driver(i2c);
+
+        ldc.set_antideform(Channel1, true).unwrap();
+        assert_eq!(ldc.i2c.get(Register::CommonDeform), 0xA7);
+
+        ldc.set_antideform(Channel0, false).unwrap();
+        assert_eq!(ldc.i2c.get(Register::CommonDeform), 0xA6);
+    }
+
+    #[test]
+    fn set_max_win_preserves_btpause_and_other_channels() {
+        let mut i2c = MockI2c::new();
+        // Baseline tracking paused on all channels.
+        i2c.set(Register::BtPauseMaxWin, 0xF0);
+        let mut ldc = config_driver(i2c);
+
+        ldc.set_max_win(Channel2, true).unwrap();
+        ldc.set_max_win(Channel1, true).unwrap();
+        assert_eq!(ldc.i2c.get(Register::BtPauseMaxWin), 0xF6);
+
+        ldc.set_max_win(Channel2, false).unwrap();
+        assert_eq!(ldc.i2c.get(Register::BtPauseMaxWin), 0xF2);
+    }
+
+    #[test]
+    fn sensor_frequency_follows_the_datasheet_formula() {
+        // With the default SENCY of 4 and LCDIV of 3, W = 128 * 5 * 2^3 = 5120,
+        // so a 3 MHz sensor counts 30 * 5120 * 44 MHz / 3 MHz = 2_252_800.
+        let raw: u32 = 2_252_800;
+        let mut i2c = MockI2c::new();
+        let [_, msb, mid, lsb] = raw.to_be_bytes();
+        i2c.set(Register::RawData0_3, msb);
+        i2c.set(Register::RawData0_2, mid);
+        i2c.set(Register::RawData0_1, lsb);
+        let mut ldc = Ldc3114::new(i2c);
+
+        assert_eq!(ldc.read_sensor_frequency(Channel0).unwrap(), 3_000_000);
+        assert_eq!(
+            ldc.sensor_frequency_hz(Channel0, raw, 40_000_000),
+            Some(2_727_272)
+        );
+
+        // LCDIV of 0 divides W, and the frequency, by 8.
+        ldc.state.lcdiv = 0;
+        assert_eq!(
+            ldc.sensor_frequency_hz(Channel0, raw, 44_000_000),
+            Some(375_000)
+        );
+    }
+
+    #[test]
+    fn set_scan_rate_reporting_returns_the_rate_sps_or_zero_when_disabled() {
+        // (enabled channels, scan rate, reported SPS)
+        let table = [
+            (EN0, ScanRate::High, 80),
+            (EN0 | EN1 | EN2 | EN3, ScanRate::Lowest, 10),
+            (EN2 | EN3, ScanRate::Highest, 160),
+            (0, ScanRate::Highest, 0),
+        ];
+
+        for (en, sr, sps) in table {
+            let mut i2c = MockI2c::new();
+            i2c.set(Register::En, en);
+            let mut ldc = config_driver(i2c);
+
+            assert_eq!(ldc.set_scan_rate_reporting(sr).unwrap(), sps);
+            assert_eq!(ldc.i2c.get(Register::NpScanRate), sr as u8);
+            assert_eq!(ldc.scan_rate().map(|sr| sr as u8), Some(sr as u8));
         }
+    }
 
-        self.i2c
-            .write(I2C_ADDR, &[register.addr(), value])
-            .map_err(Error::I2c)?;
-        Ok(())
+    #[test]
+    fn with_scan_rate_restores_the_previous_rate() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::NpScanRate, ScanRate::Medium as u8);
+        let mut ldc = Ldc3114::new(i2c);
+
+        let during = ldc
+            .with_scan_rate(ScanRate::Highest, &mut NoDelay, |ldc| {
+                for _ in 0..3 {
+                    ldc.read_data(Channel0).unwrap();
+                }
+                ldc.read_register(Register::NpScanRate).unwrap()
+            })
+            .unwrap();
+
+        assert_eq!(during, ScanRate::Highest as u8);
+        assert_eq!(ldc.i2c.get(Register::NpScanRate), ScanRate::Medium as u8);
+        assert_eq!(ldc.i2c.get(Register::Reset), 0);
+        assert!(matches!(ldc.scan_rate(), Some(ScanRate::Medium)));
     }
 
-    /// Reads a value from a given register.
-    pub fn read_register(&mut self, register: Register) -> Result<u8, Error<E>> {
-        let mut buffer = [0u8; 1];
-        self.i2c
-            .write_read(I2C_ADDR, &[register.addr()], &mut buffer)
-            .map_err(Error::I2c)?;
-        Ok(buffer[0])
+    #[test]
+    fn set_all_ftf_writes_three_registers_preserving_reserved_bits() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::Ftf0, 0xF9);
+        i2c.set(Register::Ftf1_2, 0x0F);
+        i2c.set(Register::Ftf3, 0xFC);
+        let mut ldc = config_driver(i2c);
+        ldc.i2c.writes.clear();
+
+        ldc.set_all_ftf([
+            FastTrackingFactor::Two,
+            FastTrackingFactor::One,
+            FastTrackingFactor::Three,
+            FastTrackingFactor::One,
+        ])
+        .unwrap();
+
+        let expected = [
+            (Register::Ftf0.addr(), 0xFD),
+            (Register::Ftf1_2.addr(), 0xDF),
+            (Register::Ftf3.addr(), 0xFD),
+        ];
+        assert_eq!(ldc.i2c.writes, expected);
     }
 
-    /// Modifies the value of a given register.
-    pub fn modify_register<F>(&mut self, register: Register, f: F) -> Result<(), Error<E>>
-    where
-        F: FnOnce(u8) -> u8,
-    {
-        let value = self.read_register(register)?;
-        self.write_register(register, f(value))
+    /// A device that reports `samples` as channel 0 data, one per conversion,
+    /// with 150 polls of the OUT register between conversions.
+    fn scripted_samples(samples: &'static [i16]) -> MockI2c {
+        let mut i2c = MockI2c::new();
+        let mut polls = 0;
+        let mut samples = samples.iter();
+        i2c.after_read = Some(Box::new(move |start, regs| {
+            let out = Register::Out as usize;
+            if start != Register::Out.addr() {
+                return;
+            }
+            polls += 1;
+            regs[out] = 0;
+            if polls % 150 == 0
+                && let Some(sample) = samples.next()
+            {
+                let data = Register::Data0Lsb as usize;
+                regs[data..data + 2].copy_from_slice(&sample.to_le_bytes());
+                regs[out] = DATA_RDY;
+            }
+        }));
+        i2c
     }
 
-    /// Sets some bits of a given register.
-    pub fn set_register_bits(&mut self, register: Register, bits: u8) -> Result<(), Error<E>> {
-        self.modify_register(register, |v| v | bits)
+    #[test]
+    fn measure_signal_stats_waits_for_slow_conversions() {
+        let mut ldc = Ldc3114::new(scripted_samples(&[100, 102, 97, 101]));
+
+        let stats = ldc.measure_signal_stats(Channel0, 4, &mut NoDelay).unwrap();
+
+        assert_eq!((stats.mean, stats.min, stats.max), (100, 97, 102));
+        assert_eq!(stats.peak_to_peak(), 5);
     }
 
-    /// Clears some bits of a given register.
-    pub fn clear_register_bits(&mut self, register: Register, bits: u8) -> Result<(), Error<E>> {
-        self.modify_register(register, |v| v & !bits)
+    #[cfg(feature = "float")]
+    #[test]
+    fn snr_of_scripted_measurements() {
+        let mut ldc = Ldc3114::new(scripted_samples(&[100, 102, 97, 101, 150, 150]));
+
+        let untouched = ldc.measure_signal_stats(Channel0, 4, &mut NoDelay).unwrap();
+        let touched = ldc.measure_signal_stats(Channel0, 2, &mut NoDelay).unwrap();
+
+        assert_eq!(untouched.snr(&touched), 10.0);
+        assert_eq!(touched.snr(&untouched), f32::INFINITY);
+        assert_eq!(touched.snr(&touched), f32::INFINITY);
+    }
+
+    #[test]
+    fn set_gain_preserves_reserved_bits() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::Gain2, 0xC0);
+        let mut ldc = config_driver(i2c);
+
+        ldc.set_gain(Channel2, 0x15).unwrap();
+        assert_eq!(ldc.i2c.get(Register::Gain2), 0xD5);
+
+        ldc.i2c.writes.clear();
+        assert!(matches!(
+            ldc.set_gain(Channel2, 0x40),
+            Err(Error::InvalidParameter)
+        ));
+        assert!(ldc.i2c.writes.is_empty());
+    }
+
+    #[test]
+    fn set_hysteresis_preserves_reserved_bits() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::Hyst, 0xF0);
+        let mut ldc = config_driver(i2c);
+
+        ldc.set_hysteresis(0x0A).unwrap();
+        assert_eq!(ldc.i2c.get(Register::Hyst), 0xFA);
+        assert!(matches!(
+            ldc.set_hysteresis(0x10),
+            Err(Error::InvalidParameter)
+        ));
+    }
+
+    #[test]
+    fn set_antitwist_preserves_reserved_bits() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::Twist, 0xF8);
+        let mut ldc = config_driver(i2c);
+
+        ldc.set_antitwist(0x05).unwrap();
+        assert_eq!(ldc.i2c.get(Register::Twist), 0xFD);
+        assert!(matches!(
+            ldc.set_antitwist(0x08),
+            Err(Error::InvalidParameter)
+        ));
+    }
+
+    #[test]
+    fn set_lc_divider_preserves_reserved_bits_and_updates_the_cache() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::LcDivider, 0xF8);
+        let mut ldc = config_driver(i2c);
+
+        ldc.set_lc_divider(0x02).unwrap();
+        assert_eq!(ldc.i2c.get(Register::LcDivider), 0xFA);
+        assert_eq!(ldc.state.lcdiv, 0x02);
+
+        assert!(matches!(
+            ldc.set_lc_divider(0x08),
+            Err(Error::InvalidParameter)
+        ));
+        assert_eq!(ldc.state.lcdiv, 0x02);
+    }
+
+    #[test]
+    fn hardware_reset_power_cycles_then_polls_until_ready() {
+        let log = CallLog::default();
+        let mut i2c = MockI2c::new();
+        // The device doesn't acknowledge until it has powered up.
+        i2c.nacks = 2;
+        let mut ldc = Ldc3114::new_with_reset(i2c, MockPin(log.clone()));
+        ldc.state.lcdiv = 0;
+
+        ldc.hardware_reset(&mut MockDelay(log.clone()), 20, 3)
+            .unwrap();
+
+        let expected = [
+            Call::Low,
+            Call::DelayMs(20),
+            Call::High,
+            Call::DelayMs(3),
+            Call::DelayUs(POLL_INTERVAL_US),
+            Call::DelayUs(POLL_INTERVAL_US),
+        ];
+        assert_eq!(*log.borrow(), expected);
+        assert_eq!(ldc.state.lcdiv, 3);
+    }
+
+    #[test]
+    fn probe_maps_a_missing_acknowledge_to_not_present() {
+        let mut i2c = MockI2c::new();
+        i2c.nacks = 1;
+        let mut ldc = Ldc3114::new(i2c);
+        assert!(matches!(ldc.probe(), Err(Error::NotPresent)));
+
+        // The device answers once it acknowledges.
+        assert!(ldc.probe().is_ok());
+
+        ldc.i2c.bus_error = Some(ErrorKind::ArbitrationLoss);
+        assert!(matches!(
+            ldc.probe(),
+            Err(Error::I2c(ErrorKind::ArbitrationLoss))
+        ));
+    }
+
+    #[test]
+    fn read_gains_decodes_and_masks_reserved_bits() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::Gain0, 0xFF);
+        i2c.set(Register::LpScanRate, 0x03);
+        i2c.set(Register::Gain1, 0xC1);
+        i2c.set(Register::IntPol, 0x1F);
+        i2c.set(Register::Gain2, 0xA2);
+        i2c.set(Register::Gain3, 0x7E);
+        let mut ldc = Ldc3114::new(i2c);
+
+        let gains = ldc.read_gains().unwrap();
+
+        assert_eq!(gains.as_array(), [0x3F, 0x01, 0x22, 0x3E]);
+        assert_eq!(ldc.get_gain(Channel3).unwrap(), 0x3E);
+    }
+
+    #[test]
+    fn scan_scheduled_enables_only_due_channels() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::En, 0xFF);
+        let mut ldc = Ldc3114::new(i2c);
+        let saved = ldc.save_enable_state().unwrap();
+        // Channel 0 on every tick, channel 1 on every other tick,
+        // channel 2 never and channel 3 on every third tick.
+        let mut scanner = ScheduledScanner::new([1, 2, 0, 3]);
+
+        let due: [u8; 6] = core::array::from_fn(|_| {
+            ldc.scan_scheduled(&mut scanner, &mut NoDelay)
+                .unwrap()
+                .bits()
+        });
+
+        assert_eq!(due, [0b1011, 0b0001, 0b0011, 0b1001, 0b0011, 0b0001]);
+        let en: std::vec::Vec<u8> = ldc
+            .i2c
+            .writes
+            .iter()
+            .filter(|&&(reg, _)| reg == Register::En.addr())
+            .map(|&(_, value)| value)
+            .collect();
+        // LPEN is cleared for channels that aren't due and stays cleared
+        // until the enable state is restored.
+        assert_eq!(en, [0xBB, 0x11, 0x13, 0x19, 0x13, 0x11]);
+
+        ldc.with_config_session(&mut NoDelay, |ldc| ldc.restore_enable_state(saved))
+            .unwrap();
+        assert_eq!(ldc.i2c.get(Register::En), 0xFF);
+    }
+
+    #[test]
+    fn new_attach_populates_the_cache_from_the_device() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::Reset, CONFIG_MODE);
+        i2c.set(Register::LcDivider, 0xF2);
+        i2c.set(Register::NpScanRate, 0xF0 | ScanRate::Lowest as u8);
+        i2c.set(Register::Sensor0Config, 0xE1);
+        i2c.set(Register::Sensor1Config, 0x02);
+        i2c.set(Register::Sensor2Config, 0x83);
+        i2c.set(Register::Sensor3Config, 0x1F);
+
+        let ldc = Ldc3114::new_attach(i2c).unwrap();
+
+        assert_eq!(ldc.state.lcdiv, 2);
+        assert!(matches!(ldc.scan_rate(), Some(ScanRate::Lowest)));
+        let sency = [
+            ldc.state.sency0,
+            ldc.state.sency1,
+            ldc.state.sency2,
+            ldc.state.sency3,
+        ];
+        assert_eq!(sency, [0x01, 0x02, 0x03, 0x1F]);
+        // The device was left in configuration mode.
+        assert_eq!(ldc.i2c.get(Register::Reset), 0);
+    }
+
+    #[test]
+    fn read_channel_register_dispatches_each_kind() {
+        let table = [
+            (ChannelRegister::Data, Register::Data2Lsb),
+            (ChannelRegister::RawData, Register::RawData2_3),
+            (ChannelRegister::Gain, Register::Gain2),
+            (ChannelRegister::SensorConfig, Register::Sensor2Config),
+            (ChannelRegister::Ftf, Register::Ftf1_2),
+        ];
+        let mut i2c = MockI2c::new();
+        for (value, (_, register)) in (0xA0..).zip(table) {
+            i2c.set(register, value);
+        }
+        let mut ldc = Ldc3114::new(i2c);
+
+        for (value, (which, _)) in (0xA0..).zip(table) {
+            assert_eq!(ldc.read_channel_register(Channel2, which).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn measure_all_decodes_the_status_out_and_data_block() {
+        let mut i2c = MockI2c::new();
+        let block = [
+            CHIP_READY | OUT_STATUS,
+            DATA_RDY | OUT3 | OUT0,
+            0xFF,
+            0x07,
+            0x00,
+            0x08,
+            0x34,
+            0x0F,
+            0x01,
+            0x00,
+        ];
+        i2c.regs[..block.len()].copy_from_slice(&block);
+        let mut ldc = Ldc3114::new(i2c);
+
+        let states = ldc.measure_all().unwrap();
+
+        let data = states.map(|state| state.data);
+        let pressed = states.map(|state| state.pressed);
+        assert_eq!(data, [0x7FF, -0x800, -0x0CC, 0x001]);
+        assert_eq!(pressed, [true, false, false, true]);
+        assert!(ldc.accumulated_status().output_status);
+    }
+
+    #[test]
+    fn configure_and_verify_accepts_a_matching_readback() {
+        let mut i2c = MockI2c::new();
+        // Reserved bits aren't part of the comparison.
+        i2c.set(Register::Gain1, 0xC0);
+        let mut ldc = Ldc3114::new(i2c);
+
+        ldc.configure_and_verify(&DeviceConfig::const_default(), &mut NoDelay)
+            .unwrap();
+
+        assert_eq!(ldc.i2c.get(Register::Gain1), 0xE8);
+        assert_eq!(ldc.i2c.get(Register::Reset), 0);
+    }
+
+    #[test]
+    fn configure_and_verify_rejects_a_mismatching_readback() {
+        let mut i2c = MockI2c::new();
+        // The gain of channel 0 doesn't stick once the chip is polled.
+        i2c.after_read = Some(Box::new(|start, regs| {
+            if start == Register::Status.addr() {
+                regs[Register::Gain0 as usize] = 0x00;
+            }
+        }));
+        let mut ldc = Ldc3114::new(i2c);
+
+        let result = ldc.configure_and_verify(&DeviceConfig::const_default(), &mut NoDelay);
+
+        assert!(matches!(result, Err(Error::Verification)));
+        assert_eq!(ldc.i2c.get(Register::Reset), 0);
+    }
+
+    #[test]
+    fn set_interrupt_source_selects_btn_alg_en() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::IntPol, 0x07);
+        let mut ldc = config_driver(i2c);
+
+        ldc.set_interrupt_source(InterruptSource::ButtonChange)
+            .unwrap();
+        assert_eq!(ldc.i2c.get(Register::IntPol), 0x0F);
+
+        ldc.set_interrupt_source(InterruptSource::DataReady)
+            .unwrap();
+        assert_eq!(ldc.i2c.get(Register::IntPol), 0x07);
+
+        ldc.i2c.writes.clear();
+        assert!(matches!(
+            ldc.set_interrupt_source(InterruptSource::Both),
+            Err(Error::InvalidParameter)
+        ));
+        assert!(ldc.i2c.writes.is_empty());
+    }
+
+    #[test]
+    fn read_interrupt_source_decodes_btn_alg_en() {
+        let table = [
+            (0x00, InterruptSource::DataReady),
+            (0x17, InterruptSource::DataReady),
+            (0x08, InterruptSource::ButtonChange),
+            (0x1F, InterruptSource::ButtonChange),
+        ];
+
+        for (intpol, source) in table {
+            let mut i2c = MockI2c::new();
+            i2c.set(Register::IntPol, intpol);
+            let mut ldc = Ldc3114::new(i2c);
+
+            let decoded = ldc.read_interrupt_source().unwrap();
+            assert_eq!(decoded as u8, source as u8, "INTPOL {intpol:#04x}");
+        }
+    }
+
+    #[test]
+    fn cache_dependent_methods_fail_until_refreshed() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::NpScanRate, ScanRate::High as u8);
+        i2c.set(Register::RawData0_1, 0x01);
+        let mut ldc = Ldc3114::new(i2c);
+
+        ldc.invalidate_cache();
+        assert!(matches!(
+            ldc.read_sensor_frequency(Channel0),
+            Err(Error::CacheInvalidated)
+        ));
+        assert!(ldc.sensor_frequency_hz(Channel0, 1, 1).is_none());
+        assert!(ldc.scan_rate().is_none());
+        let result = ldc.with_scan_rate(ScanRate::Highest, &mut NoDelay, |_| ());
+        assert!(matches!(result, Err(Error::CacheInvalidated)));
+
+        ldc.refresh_cache().unwrap();
+        assert!(ldc.read_sensor_frequency(Channel0).is_ok());
+        assert!(matches!(ldc.scan_rate(), Some(ScanRate::High)));
+    }
+
+    #[test]
+    fn group_setters_set_or_clear_all_bits_of_their_group() {
+        let mut i2c = MockI2c::new();
+        // Baseline tracking paused on channels 0 and 3.
+        i2c.set(Register::BtPauseMaxWin, 0x90);
+        let mut ldc = config_driver(i2c);
+
+        ldc.set_max_win_group(ChannelSet::ALL).unwrap();
+        assert_eq!(ldc.i2c.get(Register::BtPauseMaxWin), 0x9F);
+        ldc.set_max_win_group(ChannelSet::NONE).unwrap();
+        assert_eq!(ldc.i2c.get(Register::BtPauseMaxWin), 0x90);
+
+        ldc.set_anticommon_group(ChannelSet::ALL).unwrap();
+        ldc.set_antideform_group(ChannelSet::ALL).unwrap();
+        assert_eq!(ldc.i2c.get(Register::CommonDeform), 0xFF);
+        ldc.set_anticommon_group(ChannelSet::NONE).unwrap();
+        assert_eq!(ldc.i2c.get(Register::CommonDeform), 0x0F);
+        ldc.set_antideform_group(ChannelSet::NONE).unwrap();
+        assert_eq!(ldc.i2c.get(Register::CommonDeform), 0x00);
+
+        // Arrays of flags are still accepted.
+        ldc.set_antideform_group([false, true, false, true])
+            .unwrap();
+        assert_eq!(ldc.i2c.get(Register::CommonDeform), 0x0A);
+    }
+
+    #[test]
+    fn burst_readers_reject_undersized_buffers() {
+        let mut ldc = Ldc3114::new(MockI2c::new());
+
+        assert!(matches!(
+            ldc.measure_all_with_buffer(&mut [0; 9]),
+            Err(Error::InvalidParameter)
+        ));
+        assert!(matches!(
+            ldc.read_gains_with_buffer(&mut [0; 6]),
+            Err(Error::InvalidParameter)
+        ));
+
+        // Longer buffers are fine, only their start is used.
+        let mut buffer = [0xAA; 16];
+        assert!(ldc.measure_all_with_buffer(&mut buffer).is_ok());
+        assert_eq!(buffer[10..], [0xAA; 6]);
+        assert!(ldc.read_gains_with_buffer(&mut buffer).is_ok());
+    }
+
+    #[test]
+    fn i2c_mut_borrows_the_bus_without_disturbing_the_driver() {
+        use embedded_hal::i2c::{I2c, NoAcknowledgeSource};
+
+        let mut ldc = Ldc3114::new(MockI2c::new());
+
+        // There is no other device on the mock bus.
+        let result = ldc.i2c_mut().write(0x50, &[0x00, 0x42]);
+        assert_eq!(
+            result,
+            Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))
+        );
+        let mut id = [0; 2];
+        ldc.i2c_mut()
+            .write_read(I2C_ADDR, &[Register::ManufacturerIdLsb.addr()], &mut id)
+            .unwrap();
+        assert_eq!(id, [0x49, 0x54]);
+
+        assert!(ldc.verify_device().is_ok());
+        assert!(ldc.i2c.writes.is_empty());
+    }
+
+    #[test]
+    fn has_been_ready_latches_chip_ready() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::Status, 0);
+        let mut ldc = Ldc3114::new(i2c);
+
+        ldc.read_status().unwrap();
+        assert!(!ldc.has_been_ready());
+
+        ldc.i2c.set(Register::Status, CHIP_READY);
+        ldc.read_status().unwrap();
+        assert!(ldc.has_been_ready());
+
+        // A later glitch doesn't clear the flag.
+        ldc.i2c.set(Register::Status, 0);
+        assert!(!ldc.read_status().unwrap().chip_ready);
+        assert!(ldc.has_been_ready());
+    }
+
+    #[test]
+    fn read_all_ftf_decodes_the_shared_register() {
+        let mut i2c = MockI2c::new();
+        // The reserved bits of all three registers are set.
+        i2c.set(Register::Ftf0, 0xFB);
+        i2c.set(Register::Ftf1_2, 0xBF);
+        i2c.set(Register::Ftf3, 0xFC);
+        let mut ldc = Ldc3114::new(i2c);
+
+        let ftf = ldc.read_all_ftf().unwrap();
+
+        assert_eq!(ftf.map(|ftf| ftf as u8), [1, 3, 2, 0]);
+    }
+
+    #[test]
+    fn disable_channel_fully_clears_enables_and_writes_safe_values() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::En, 0xFF);
+        // Reserved bits set, maximum gain.
+        i2c.set(Register::Gain2, 0xFF);
+        i2c.set(Register::Sensor2Config, 0xE4);
+        let mut ldc = config_driver(i2c);
+        ldc.i2c.writes.clear();
+
+        ldc.disable_channel_fully(Channel2).unwrap();
+
+        let expected = [
+            (Register::En.addr(), 0xBB),
+            (Register::Gain2.addr(), 0xC0),
+            (Register::Sensor2Config.addr(), 0x00),
+        ];
+        assert_eq!(ldc.i2c.writes, expected);
+    }
+
+    #[test]
+    fn check_against_golden_detects_a_changed_register() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::En, 0xFF);
+        i2c.set(Register::Ftf3, 0x02);
+        let mut ldc = Ldc3114::new(i2c);
+
+        let golden = ldc.capture_golden().unwrap();
+        assert_eq!(golden.as_bytes()[0], 0xFF);
+        assert_eq!(golden.as_bytes()[23], 0x02);
+
+        // Data registers aren't part of the configuration.
+        ldc.i2c.set(Register::Data0Lsb, 0x42);
+        assert!(ldc.check_against_golden(&golden).unwrap());
+
+        ldc.i2c.set(Register::Sensor3Config, 0x01);
+        assert!(!ldc.check_against_golden(&golden).unwrap());
+    }
+
+    #[test]
+    fn verify_device_reports_the_ids_of_an_unexpected_device() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::ManufacturerIdLsb, 0x4D);
+        i2c.set(Register::ManufacturerIdMsb, 0x41);
+        i2c.set(Register::DeviceIdLsb, 0x34);
+        i2c.set(Register::DeviceIdMsb, 0x12);
+        let mut ldc = Ldc3114::new(i2c);
+
+        assert!(matches!(
+            ldc.verify_device(),
+            Err(Error::UnexpectedDevice {
+                manufacturer_id: 0x414D,
+                device_id: 0x1234,
+            })
+        ));
+        assert!(matches!(ldc.probe(), Err(Error::UnexpectedDevice { .. })));
+    }
+
+    #[test]
+    fn clear_output_status_keeps_the_error_flags_in_the_accumulator() {
+        use crate::register::{FSM_WD, TIMEOUT};
+
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::Status, CHIP_READY | OUT_STATUS | FSM_WD | TIMEOUT);
+        let mut ldc = Ldc3114::new(i2c);
+        let mut acc = StatusAccumulator::new();
+
+        ldc.clear_output_status(&mut acc).unwrap();
+        // The device cleared the flags on read.
+        ldc.i2c.set(Register::Status, CHIP_READY);
+        ldc.clear_output_status(&mut acc).unwrap();
+
+        let status = acc.take();
+        assert!(status.output_status);
+        assert!(status.fsm_watchdog_error && status.button_timeout);
+        assert!(!acc.status().fsm_watchdog_error);
+        // The driver accumulated the same reads.
+        assert!(ldc.accumulated_status().fsm_watchdog_error);
+    }
+
+    #[test]
+    fn read_data_with_age_measures_time_since_data_ready() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::Data1Lsb, 0x2A);
+        let mut ldc = Ldc3114::new(i2c);
+        let mut tracker = DataAgeTracker::new();
+
+        // No new data reported yet.
+        let (data, age) = ldc
+            .read_data_with_age(Channel1, 50u32, &mut tracker)
+            .unwrap();
+        assert_eq!((data, age), (0x2A, None));
+
+        let mut age_at = |now, out| {
+            ldc.i2c.set(Register::Out, out);
+            let (_, age) = ldc.read_data_with_age(Channel1, now, &mut tracker).unwrap();
+            age
+        };
+        assert_eq!(age_at(100, DATA_RDY), Some(0));
+        assert_eq!(age_at(130, 0), Some(30));
+        assert_eq!(age_at(175, 0), Some(75));
+        assert_eq!(age_at(200, DATA_RDY), Some(0));
+        assert_eq!(tracker.last_data_ready(), Some(200));
+    }
+
+    #[test]
+    fn range_setters_preserve_the_cycle_count_of_each_channel() {
+        fn check<T: ChannelRegisters + Copy>(ch: T) {
+            let register = ch.sensor_config();
+            let mut i2c = MockI2c::new();
+            i2c.set(register, 0x15);
+            let mut ldc = config_driver(i2c);
+
+            ldc.set_rp_range(ch, RpRange::Rp800OhmTo10kOhm).unwrap();
+            assert_eq!(ldc.i2c.get(register), 0x95, "channel {}", T::CH);
+            ldc.set_frequency_range(ch, FrequencyRange::Freq10MHzTo30MHz)
+                .unwrap();
+            assert_eq!(ldc.i2c.get(register), 0xD5, "channel {}", T::CH);
+            ldc.set_rp_range(ch, RpRange::Rp50OhmTo4kOhm).unwrap();
+            assert_eq!(ldc.i2c.get(register), 0x55, "channel {}", T::CH);
+        }
+
+        check(Channel0);
+        check(Channel1);
+        check(Channel2);
+        check(Channel3);
+    }
+
+    #[test]
+    fn read_status_raw_matches_the_decoded_flags() {
+        let mut ldc = Ldc3114::new(MockI2c::new());
+
+        // One bit at a time, from OUT_STATUS (bit 7) to REGISTER_FLAG (bit 0).
+        for bit in 0..8 {
+            let sr = 0x80 >> bit;
+            ldc.i2c.set(Register::Status, sr);
+
+            let (raw, status) = ldc.read_status_raw().unwrap();
+
+            let flags = [
+                status.output_status,
+                status.chip_ready,
+                status.ready_to_write,
+                status.maximum_output_code,
+                status.fsm_watchdog_error,
+                status.lc_sensor_watchdog_error,
+                status.button_timeout,
+                status.register_integrity_bad,
+            ];
+            assert_eq!(raw, sr);
+            assert_eq!(
+                flags.map(u8::from),
+                core::array::from_fn(|i| u8::from(i == bit))
+            );
+        }
+    }
+
+    #[test]
+    fn read_raw_config_block_is_in_address_order() {
+        let mut i2c = MockI2c::new();
+        for (addr, value) in i2c.regs.iter_mut().enumerate() {
+            *value = !(addr as u8);
+        }
+        let mut ldc = Ldc3114::new(i2c);
+        let mut block = [0; CONFIG_BLOCK_LEN];
+
+        ldc.read_raw_config_block(&mut block).unwrap();
+
+        let expected: [u8; CONFIG_BLOCK_LEN] = core::array::from_fn(|i| !(0x0C + i as u8));
+        assert_eq!(block, expected);
+        assert_eq!(config_block_value(&block, Register::En), Some(!0x0C));
+        assert_eq!(config_block_value(&block, Register::Cntsc), Some(!0x1E));
+    }
+
+    #[test]
+    fn channel_health_flags_a_channel_without_raw_data() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::En, 0x0F);
+        i2c.set(Register::RawData0_2, 0x10);
+        i2c.set(Register::Data0Lsb, 0x20);
+        // Channel 1 has no raw data.
+        i2c.set(Register::Data1Lsb, 0x20);
+        // Channel 3 is railed at -0x800.
+        i2c.set(Register::RawData3_1, 0x01);
+        i2c.set(Register::Data3Msb, 0x08);
+        let mut ldc = Ldc3114::new(i2c);
+
+        assert!(ldc.channel_health(Channel0).unwrap().is_healthy());
+
+        let health = ldc.channel_health(Channel1).unwrap();
+        assert!(health.enabled && !health.oscillating && !health.railed);
+        assert!(!health.is_healthy());
+
+        let health = ldc.channel_health(Channel3).unwrap();
+        assert!(health.enabled && health.oscillating && health.railed);
+        assert!(!health.is_healthy());
+    }
+
+    #[test]
+    fn scan_buttons_tracks_debounced_presses_and_releases() {
+        const F: bool = false;
+        const T: bool = true;
+
+        let mut ldc = Ldc3114::new(MockI2c::new());
+        let mut scanner = ButtonScanner::new(2);
+
+        // OUT, then the expected states, pressed edges and released edges.
+        let scans = [
+            // A single-scan glitch on channel 3 is rejected.
+            (OUT0 | OUT3, [F, F, F, F], [F, F, F, F], [F, F, F, F]),
+            (OUT0, [T, F, F, F], [T, F, F, F], [F, F, F, F]),
+            (OUT0, [T, F, F, F], [F, F, F, F], [F, F, F, F]),
+            (0, [T, F, F, F], [F, F, F, F], [F, F, F, F]),
+            (OUT3, [F, F, F, F], [F, F, F, F], [T, F, F, F]),
+            (OUT3, [F, F, F, T], [F, F, F, T], [F, F, F, F]),
+        ];
+        for (i, (out, pressed, pressed_edges, released_edges)) in scans.into_iter().enumerate() {
+            ldc.i2c.set(Register::Out, out);
+
+            let scan = ldc.scan_buttons(&mut scanner).unwrap();
+
+            assert_eq!(scan.pressed, pressed, "scan {i}");
+            assert_eq!(scan.pressed_edges, pressed_edges, "scan {i}");
+            assert_eq!(scan.released_edges, released_edges, "scan {i}");
+            assert_eq!(scanner.pressed(), pressed, "scan {i}");
+        }
+    }
+
+    #[test]
+    fn read_press_state_applies_hysteresis_to_a_ramp() {
+        let mut ldc = Ldc3114::new(MockI2c::new());
+
+        // Ramp up through the threshold, then down through the hysteresis.
+        let ramp = [
+            (-50i16, false),
+            (50, false),
+            (99, false),
+            (100, true),
+            (150, true),
+            (90, true),
+            (80, true),
+            (79, false),
+            (90, false),
+            (100, true),
+        ];
+        for (data, pressed) in ramp {
+            let [lsb, msb] = data.to_le_bytes();
+            ldc.i2c.set(Register::Data0Lsb, lsb);
+            ldc.i2c.set(Register::Data0Msb, msb & 0x0F);
+
+            let state = ldc.read_press_state(Channel0, 100, 20).unwrap();
+            assert_eq!(state, pressed, "data {data}");
+        }
+
+        // Each channel has its own state.
+        ldc.i2c.set(Register::Data1Lsb, 90);
+        assert!(!ldc.read_press_state(Channel1, 100, 20).unwrap());
+    }
+
+    #[test]
+    fn restore_enable_state_restores_the_saved_byte() {
+        let mut i2c = MockI2c::new();
+        // Channels 0 and 2 in normal mode, channel 3 also in low power mode.
+        i2c.set(Register::En, 0x8D);
+        let mut ldc = config_driver(i2c);
+
+        let saved = ldc.save_enable_state().unwrap();
+        ldc.disable_all_channels().unwrap();
+        assert_eq!(ldc.i2c.get(Register::En), 0x00);
+        ldc.restore_enable_state(saved).unwrap();
+
+        assert_eq!(saved, 0x8D);
+        assert_eq!(ldc.i2c.get(Register::En), 0x8D);
+    }
+
+    #[test]
+    fn enable_all_channels_writes_the_en_byte_of_each_mode() {
+        let mut ldc = config_driver(MockI2c::new());
+        ldc.i2c.writes.clear();
+
+        ldc.enable_all_channels(ChannelMode::NormalAndLowPowerMode)
+            .unwrap();
+        ldc.enable_all_channels(ChannelMode::NormalMode).unwrap();
+        ldc.enable_all_channels(ChannelMode::Disabled).unwrap();
+        ldc.disable_all_channels().unwrap();
+
+        let en = Register::En.addr();
+        assert_eq!(
+            ldc.i2c.writes,
+            [(en, 0xFF), (en, 0x0F), (en, 0x00), (en, 0x00)]
+        );
+    }
+
+    #[test]
+    fn read_raw_data_settled_retries_while_zero() {
+        let mut i2c = MockI2c::new();
+        let mut reads = 0;
+        // The raw data settles after the second read.
+        i2c.after_read = Some(Box::new(move |start, regs| {
+            if start == Register::RawData1_3.addr() {
+                reads += 1;
+                if reads == 2 {
+                    regs[Register::RawData1_1 as usize] = 0x42;
+                }
+            }
+        }));
+        let mut ldc = Ldc3114::new(i2c);
+        let log = CallLog::default();
+
+        let data = ldc
+            .read_raw_data_settled(Channel1, &mut MockDelay(log.clone()), 5)
+            .unwrap();
+
+        assert_eq!(data, 0x42);
+        assert_eq!(*log.borrow(), [Call::DelayUs(1000), Call::DelayUs(1000)]);
+
+        // A channel that stays at zero is retried until exhausted.
+        log.borrow_mut().clear();
+        let data = ldc
+            .read_raw_data_settled(Channel2, &mut MockDelay(log.clone()), 3)
+            .unwrap();
+        assert_eq!(data, 0);
+        assert_eq!(log.borrow().len(), 3);
+    }
+
+    #[test]
+    fn production_bringup_reports_passing_and_failing_units() {
+        use crate::register::LC_WD;
+
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::Data2Lsb, 0x7B);
+        i2c.set(Register::Out, OUT3);
+        let mut ldc = Ldc3114::new(i2c);
+
+        let report = ldc
+            .production_bringup(&DeviceConfig::const_default(), &mut NoDelay)
+            .unwrap();
+
+        assert!(report.passed());
+        assert!(report.config_verified);
+        assert_eq!(report.channels[2].data, 0x7B);
+        assert!(report.channels[3].pressed);
+
+        // An oscillator failed to start on this unit. The flag is only
+        // reported by the first status read.
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::Status, CHIP_READY | LC_WD);
+        i2c.after_read = Some(Box::new(|start, regs| {
+            if start == Register::Status.addr() {
+                regs[Register::Status as usize] &= !LC_WD;
+            }
+        }));
+        let mut ldc = Ldc3114::new(i2c);
+
+        let report = ldc
+            .production_bringup(&DeviceConfig::const_default(), &mut NoDelay)
+            .unwrap();
+
+        assert!(!report.passed());
+        assert!(report.config_verified);
+        assert!(report.status.lc_sensor_watchdog_error);
+    }
+
+    #[test]
+    fn full_id_packs_the_manufacturer_id_in_the_high_half() {
+        let mut ldc = Ldc3114::new(MockI2c::new());
+
+        assert_eq!(ldc.full_id().unwrap(), EXPECTED_FULL_ID);
+
+        ldc.i2c.set(Register::ManufacturerIdLsb, 0x01);
+        ldc.i2c.set(Register::ManufacturerIdMsb, 0x02);
+        ldc.i2c.set(Register::DeviceIdLsb, 0x03);
+        ldc.i2c.set(Register::DeviceIdMsb, 0x04);
+        let id = ldc.full_id().unwrap();
+        assert_eq!(id, 0x0201_0403);
+        assert_ne!(id, EXPECTED_FULL_ID);
+    }
+
+    #[test]
+    fn is_baseline_frozen_requires_btpause_and_an_asserted_output() {
+        let mut ldc = Ldc3114::new(MockI2c::new());
+
+        // Channel 1 is frozen only when both its BTPAUSE1 (0x20) and OUT1
+        // (0x02) bits are set, regardless of the other channels.
+        let cases = [
+            (0xD0, 0x0D, false),
+            (0xF0, 0x0D, false),
+            (0xD0, 0x0F, false),
+            (0x20, 0x02, true),
+        ];
+        for (btpause, out, frozen) in cases {
+            ldc.i2c.set(Register::BtPauseMaxWin, btpause);
+            ldc.i2c.set(Register::Out, out);
+
+            let result = ldc.is_baseline_frozen(Channel1).unwrap();
+            assert_eq!(result, frozen, "BTPAUSE {btpause:#04x}, OUT {out:#04x}");
+        }
+    }
+
+    #[test]
+    fn read_data_scaled_maps_the_code_range_onto_the_full_scale() {
+        let mut ldc = Ldc3114::new(MockI2c::new());
+        let cases = [
+            (0x7FFi16, 1000, 1000),
+            (-0x800, 1000, -1000),
+            (0x400, 1000, 500),
+            (-0x400, 1000, -500),
+            (0, 1000, 0),
+            // Large full scales don't overflow.
+            (0x7FF, i32::MAX, i32::MAX),
+            (-0x800, i32::MAX, -i32::MAX),
+        ];
+        for (data, full_scale, scaled) in cases {
+            let [lsb, msb] = data.to_le_bytes();
+            ldc.i2c.set(Register::Data0Lsb, lsb);
+            ldc.i2c.set(Register::Data0Msb, msb & 0x0F);
+
+            let result = ldc.read_data_scaled(Channel0, full_scale).unwrap();
+            assert_eq!(result, scaled, "data {data}, full scale {full_scale}");
+        }
+
+        assert!(matches!(
+            ldc.read_data_scaled(Channel0, 0),
+            Err(Error::InvalidParameter)
+        ));
+    }
+
+    #[test]
+    fn is_scanning_detects_changing_raw_data() {
+        let mut i2c = MockI2c::new();
+        i2c.set(Register::RawData3_2, 0x10);
+        let mut reads = 0;
+        // A new conversion lands after the third read.
+        i2c.after_read = Some(Box::new(move |start, regs| {
+            if start == Register::RawData3_3.addr() {
+                reads += 1;
+                if reads == 3 {
+                    regs[Register::RawData3_1 as usize] = 0x01;
+                }
+            }
+        }));
+        let mut ldc = Ldc3114::new(i2c);
+        let log = CallLog::default();
+
+        assert!(
+            ldc.is_scanning(Channel3, &mut MockDelay(log.clone()))
+                .unwrap()
+        );
+        assert_eq!(log.borrow().len(), 3);
+
+        // The raw data of channel 0 never changes.
+        log.borrow_mut().clear();
+        assert!(
+            !ldc.is_scanning(Channel0, &mut MockDelay(log.clone()))
+                .unwrap()
+        );
+        assert_eq!(log.borrow().len(), MAX_POLLS as usize);
+    }
+
+    #[test]
+    fn with_button_algorithm_disabled_restores_btn_alg_en() {
+        for intpol in [BTSRT_EN | BTN_ALG_EN, BTSRT_EN] {
+            let mut i2c = MockI2c::new();
+            i2c.set(Register::IntPol, intpol);
+            i2c.set(Register::RawData0_1, 0x2A);
+            let mut ldc = Ldc3114::new(i2c);
+
+            let raw = ldc
+                .with_button_algorithm_disabled(&mut NoDelay, |ldc| {
+                    assert_eq!(ldc.i2c.get(Register::IntPol), BTSRT_EN);
+                    [ldc.read_raw_data(Channel0), ldc.read_raw_data(Channel0)]
+                })
+                .unwrap();
+
+            assert!(raw.iter().all(|raw| matches!(raw, Ok(0x2A))));
+            assert_eq!(ldc.i2c.get(Register::IntPol), intpol);
+            assert_eq!(ldc.i2c.get(Register::Reset), 0);
+        }
     }
 }